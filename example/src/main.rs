@@ -1,11 +1,14 @@
 use std::env;
 
 use jupiter_swap_api_client::{
-    quote::QuoteRequest, swap::SwapRequest, transaction_config::TransactionConfig,
+    dexes::{DexLabel, DexSet},
+    quote::QuoteRequest,
+    swap::SwapRequest,
+    transaction_config::TransactionConfig,
     JupiterSwapApiClient,
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{pubkey, transaction::VersionedTransaction};
+use solana_sdk::pubkey;
 use solana_sdk::{pubkey::Pubkey, signature::NullSigner};
 
 const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
@@ -18,20 +21,28 @@ async fn main() {
     let api_base_url = env::var("API_BASE_URL").unwrap_or("https://quote-api.jup.ag/v6".into());
     println!("Using base url: {}", api_base_url);
 
-    let jupiter_swap_api_client = JupiterSwapApiClient::new(api_base_url).unwrap();
+    let mut builder = JupiterSwapApiClient::builder(api_base_url);
+    if let Ok(api_key) = env::var("API_KEY") {
+        builder = builder.api_key(api_key);
+    }
+    let jupiter_swap_api_client = builder.build().unwrap();
 
-    let quote_request = QuoteRequest {
-        amount: 1_000_000,
-        input_mint: USDC_MINT,
-        output_mint: NATIVE_MINT,
-        dexes: Some("Whirlpool,Meteora DLMM,Raydium CLMM".into()),
-        slippage_bps: 50,
-        ..QuoteRequest::default()
-    };
+    let quote_request = QuoteRequest::builder()
+        .amount(1_000_000)
+        .input_mint(USDC_MINT)
+        .output_mint(NATIVE_MINT)
+        .dexes(DexSet::from_iter([
+            DexLabel::Whirlpool,
+            DexLabel::MeteoraDlmm,
+            DexLabel::RaydiumClmm,
+        ]))
+        .slippage_bps(50)
+        .build()
+        .unwrap();
 
     // GET /quote
     let quote_response = jupiter_swap_api_client.quote(quote_request).await.unwrap();
-    println!("{quote_response:#?}");
+    println!("{}", quote_response.route_tree());
 
     // POST /swap
     let swap_response = jupiter_swap_api_client
@@ -48,13 +59,9 @@ async fn main() {
 
     println!("Raw tx len: {}", swap_response.swap_transaction.len());
 
-    let versioned_transaction: VersionedTransaction =
-        bincode::deserialize(&swap_response.swap_transaction).unwrap();
-
     // Replace with a keypair or other struct implementing signer
     let null_signer = NullSigner::new(&TEST_WALLET);
-    let signed_versioned_transaction =
-        VersionedTransaction::try_new(versioned_transaction.message, &[&null_signer]).unwrap();
+    let signed_versioned_transaction = swap_response.sign(&[&null_signer]).unwrap();
 
     // send with rpc client...
     let rpc_client = RpcClient::new("https://api.mainnet-beta.solana.com".into());
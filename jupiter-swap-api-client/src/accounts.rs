@@ -0,0 +1,46 @@
+//! Access to on-chain accounts, injected so that the client does not depend on an RPC client
+//!
+
+use std::{collections::HashMap, convert::Infallible, future::Future};
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Fetch the data of on-chain accounts, typically backed by `getMultipleAccounts`
+pub trait AccountFetcher {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Data of each account in the order of `pubkeys`, None when the account does not exist
+    fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> impl Future<Output = Result<Vec<Option<Vec<u8>>>, Self::Error>> + Send;
+}
+
+/// Accounts known ahead of time, missing accounts are reported as not existing
+impl AccountFetcher for HashMap<Pubkey, Vec<u8>> {
+    type Error = Infallible;
+
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.get(pubkey).cloned())
+            .collect())
+    }
+}
+
+/// Size of an SPL token mint, token 2022 mints with extensions are larger
+const MINT_LEN: usize = 82;
+/// Offset of the decimals in a mint, after the mint authority and the supply
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+
+/// Decimals of an initialized SPL token or token 2022 mint
+pub fn mint_decimals(data: &[u8]) -> Option<u8> {
+    if data.len() < MINT_LEN || data[MINT_IS_INITIALIZED_OFFSET] != 1 {
+        return None;
+    }
+    Some(data[MINT_DECIMALS_OFFSET])
+}
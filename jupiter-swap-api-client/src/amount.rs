@@ -0,0 +1,237 @@
+//! Token amounts in base units along with the decimals of their mint, to convert from and to UI amounts
+//!
+
+use std::{collections::HashMap, fmt};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::{
+    accounts::{mint_decimals, AccountFetcher},
+    quote::{PlatformFee, QuoteRequest, QuoteRequestBuilder, QuoteResponse, SwapMode},
+    route_plan_with_metadata::SwapInfo,
+};
+
+/// Max decimals that a [`Decimal`] can represent without losing precision
+pub const MAX_DECIMALS: u8 = 28;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AmountError {
+    #[error("{0} decimals are above the max of {MAX_DECIMALS}")]
+    TooManyDecimals(u8),
+    #[error("Amount {0} is negative")]
+    Negative(Decimal),
+    #[error("Amount {amount} has more than {decimals} decimals")]
+    Inexact { amount: Decimal, decimals: u8 },
+    #[error("Amount {0} does not fit in a u64 of base units")]
+    Overflow(Decimal),
+    #[error("Decimals of mint {0} are unknown")]
+    MissingDecimals(Pubkey),
+}
+
+/// Amount in base units of a mint with `decimals`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TokenAmount {
+    pub raw: u64,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Base units of a UI amount, failing rather than rounding when the amount has more decimals than the mint
+    pub fn from_decimal(amount: Decimal, decimals: u8) -> Result<Self, AmountError> {
+        if decimals > MAX_DECIMALS {
+            return Err(AmountError::TooManyDecimals(decimals));
+        }
+        if amount.is_sign_negative() && !amount.is_zero() {
+            return Err(AmountError::Negative(amount));
+        }
+        let scaled = amount
+            .checked_mul(Decimal::from_i128_with_scale(
+                10i128.pow(u32::from(decimals)),
+                0,
+            ))
+            .ok_or(AmountError::Overflow(amount))?;
+        if !scaled.fract().is_zero() {
+            return Err(AmountError::Inexact { amount, decimals });
+        }
+        let raw = u64::try_from(scaled.trunc()).map_err(|_| AmountError::Overflow(amount))?;
+        Ok(Self { raw, decimals })
+    }
+
+    /// UI amount, exact as long as the decimals are supported
+    pub fn to_decimal(&self) -> Result<Decimal, AmountError> {
+        Decimal::try_from_i128_with_scale(i128::from(self.raw), u32::from(self.decimals))
+            .map_err(|_| AmountError::TooManyDecimals(self.decimals))
+    }
+
+    /// Amount of `mint` with its decimals looked up in `decimals`
+    pub fn of_mint(
+        raw: u64,
+        mint: &Pubkey,
+        decimals: &HashMap<Pubkey, u8>,
+    ) -> Result<Self, AmountError> {
+        let decimals = *decimals
+            .get(mint)
+            .ok_or(AmountError::MissingDecimals(*mint))?;
+        Ok(Self::new(raw, decimals))
+    }
+}
+
+impl TryFrom<TokenAmount> for Decimal {
+    type Error = AmountError;
+
+    fn try_from(amount: TokenAmount) -> Result<Self, Self::Error> {
+        amount.to_decimal()
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_decimal() {
+            Ok(amount) => write!(f, "{amount}"),
+            Err(_) => write!(f, "{}e-{}", self.raw, self.decimals),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum UiAmountError {
+    #[error(transparent)]
+    Amount(#[from] AmountError),
+    #[error("Failed to fetch mint {mint}: {source}")]
+    Fetch {
+        mint: Pubkey,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Mint {0} does not exist")]
+    MintNotFound(Pubkey),
+    #[error("Account {0} is not a mint")]
+    InvalidMint(Pubkey),
+}
+
+impl QuoteRequest {
+    /// Builder of a quote request for a UI amount of the input mint when ExactIn, of the output mint
+    /// when ExactOut, the slippage and the other parameters are set on it before building
+    pub fn from_ui_amount(
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        swap_mode: SwapMode,
+        ui_amount: Decimal,
+        decimals: &HashMap<Pubkey, u8>,
+    ) -> Result<QuoteRequestBuilder, UiAmountError> {
+        let amount_mint = match swap_mode {
+            SwapMode::ExactIn => input_mint,
+            SwapMode::ExactOut => output_mint,
+        };
+        let decimals = *decimals
+            .get(&amount_mint)
+            .ok_or(AmountError::MissingDecimals(amount_mint))?;
+        let amount = TokenAmount::from_decimal(ui_amount, decimals)?;
+        Ok(Self::builder()
+            .input_mint(input_mint)
+            .output_mint(output_mint)
+            .swap_mode(swap_mode)
+            .amount(amount.raw))
+    }
+
+    /// Same as [`QuoteRequest::from_ui_amount`] with the decimals read from the on-chain mint
+    pub async fn from_ui_amount_fetched<F: AccountFetcher>(
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        swap_mode: SwapMode,
+        ui_amount: Decimal,
+        account_fetcher: &F,
+    ) -> Result<QuoteRequestBuilder, UiAmountError> {
+        let amount_mint = match swap_mode {
+            SwapMode::ExactIn => input_mint,
+            SwapMode::ExactOut => output_mint,
+        };
+        let data = account_fetcher
+            .get_multiple_accounts(&[amount_mint])
+            .await
+            .map_err(|error| UiAmountError::Fetch {
+                mint: amount_mint,
+                source: Box::new(error),
+            })?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or(UiAmountError::MintNotFound(amount_mint))?;
+        let decimals = mint_decimals(&data).ok_or(UiAmountError::InvalidMint(amount_mint))?;
+        Self::from_ui_amount(
+            input_mint,
+            output_mint,
+            swap_mode,
+            ui_amount,
+            &HashMap::from([(amount_mint, decimals)]),
+        )
+    }
+}
+
+impl QuoteResponse {
+    pub fn in_ui_amount(&self, decimals: &HashMap<Pubkey, u8>) -> Result<Decimal, AmountError> {
+        TokenAmount::of_mint(self.in_amount, &self.input_mint, decimals)?.to_decimal()
+    }
+
+    pub fn out_ui_amount(&self, decimals: &HashMap<Pubkey, u8>) -> Result<Decimal, AmountError> {
+        TokenAmount::of_mint(self.out_amount, &self.output_mint, decimals)?.to_decimal()
+    }
+
+    /// Threshold in the output mint when ExactIn, in the input mint when ExactOut
+    pub fn other_amount_threshold_ui_amount(
+        &self,
+        decimals: &HashMap<Pubkey, u8>,
+    ) -> Result<Decimal, AmountError> {
+        let mint = match self.swap_mode {
+            SwapMode::ExactIn => &self.output_mint,
+            SwapMode::ExactOut => &self.input_mint,
+        };
+        TokenAmount::of_mint(self.other_amount_threshold, mint, decimals)?.to_decimal()
+    }
+
+    /// Platform fee in the output mint when ExactIn, in the input mint when ExactOut
+    pub fn platform_fee_ui_amount(
+        &self,
+        decimals: &HashMap<Pubkey, u8>,
+    ) -> Result<Option<Decimal>, AmountError> {
+        let Some(platform_fee) = &self.platform_fee else {
+            return Ok(None);
+        };
+        let mint = match self.swap_mode {
+            SwapMode::ExactIn => &self.output_mint,
+            SwapMode::ExactOut => &self.input_mint,
+        };
+        TokenAmount::of_mint(platform_fee.amount, mint, decimals)?
+            .to_decimal()
+            .map(Some)
+    }
+}
+
+impl SwapInfo {
+    pub fn in_ui_amount(&self, decimals: &HashMap<Pubkey, u8>) -> Result<Decimal, AmountError> {
+        TokenAmount::of_mint(self.in_amount, &self.input_mint, decimals)?.to_decimal()
+    }
+
+    pub fn out_ui_amount(&self, decimals: &HashMap<Pubkey, u8>) -> Result<Decimal, AmountError> {
+        TokenAmount::of_mint(self.out_amount, &self.output_mint, decimals)?.to_decimal()
+    }
+
+    pub fn fee_ui_amount(&self, decimals: &HashMap<Pubkey, u8>) -> Result<Decimal, AmountError> {
+        TokenAmount::of_mint(self.fee_amount, &self.fee_mint, decimals)?.to_decimal()
+    }
+}
+
+impl PlatformFee {
+    /// The platform fee is taken in the output mint when ExactIn and in the input mint when ExactOut,
+    /// pass the decimals of that mint
+    pub fn ui_amount(&self, decimals: u8) -> Result<Decimal, AmountError> {
+        TokenAmount::new(self.amount, decimals).to_decimal()
+    }
+}
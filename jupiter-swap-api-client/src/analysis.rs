@@ -0,0 +1,188 @@
+//! Prices, fees and price impact of a quote
+//!
+
+use std::collections::{BTreeMap, HashMap};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::{
+    amount::{AmountError, TokenAmount},
+    quote::{QuoteResponse, SwapMode},
+    serde_helpers::field_as_string,
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AnalysisError {
+    #[error(transparent)]
+    Amount(#[from] AmountError),
+    #[error("Quote has a zero {0} amount, the price is undefined")]
+    ZeroAmount(&'static str),
+    #[error("Price does not fit in a decimal")]
+    PriceOverflow,
+    #[error("Fees of mint {0} overflow")]
+    FeeOverflow(Pubkey),
+    #[error("Price impact of {price_impact_pct} is above the limit of {max_price_impact_pct}")]
+    PriceImpactTooHigh {
+        price_impact_pct: Decimal,
+        max_price_impact_pct: Decimal,
+    },
+}
+
+/// LP fees of all the route steps charged in the same mint
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LpFee {
+    #[serde(with = "field_as_string")]
+    pub mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub amount: u64,
+    /// None when the decimals of the mint were not provided
+    pub ui_amount: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformFeeShare {
+    #[serde(with = "field_as_string")]
+    pub mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub amount: u64,
+    pub ui_amount: Decimal,
+    pub fee_bps: u8,
+    /// Fee over the out amount when ExactIn, over the in amount when ExactOut
+    pub share: Decimal,
+}
+
+/// Analysis of a quote in UI amounts, prices are in output mint per input mint
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteAnalysis {
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    pub swap_mode: SwapMode,
+    pub in_amount: Decimal,
+    pub out_amount: Decimal,
+    /// Price of the quoted amounts
+    pub effective_price: Decimal,
+    /// Price once the slippage is fully used, at the min out amount when ExactIn
+    /// and at the max in amount when ExactOut
+    pub worst_case_price: Decimal,
+    /// Sorted by mint
+    pub lp_fees: Vec<LpFee>,
+    pub platform_fee: Option<PlatformFeeShare>,
+    pub price_impact_pct: Decimal,
+}
+
+impl QuoteAnalysis {
+    /// `decimals` must contain the input and output mints, LP fees in other mints are kept in base units only
+    pub fn new(
+        quote_response: &QuoteResponse,
+        decimals: &HashMap<Pubkey, u8>,
+    ) -> Result<Self, AnalysisError> {
+        let in_amount = quote_response.in_ui_amount(decimals)?;
+        let out_amount = quote_response.out_ui_amount(decimals)?;
+        let threshold = quote_response.other_amount_threshold_ui_amount(decimals)?;
+        if in_amount.is_zero() {
+            return Err(AnalysisError::ZeroAmount("in"));
+        }
+
+        let effective_price = out_amount
+            .checked_div(in_amount)
+            .ok_or(AnalysisError::PriceOverflow)?;
+        let worst_case_price = match quote_response.swap_mode {
+            SwapMode::ExactIn => threshold.checked_div(in_amount),
+            SwapMode::ExactOut => {
+                if threshold.is_zero() {
+                    return Err(AnalysisError::ZeroAmount("max in"));
+                }
+                out_amount.checked_div(threshold)
+            }
+        }
+        .ok_or(AnalysisError::PriceOverflow)?;
+
+        let mut lp_fees = BTreeMap::<Pubkey, u64>::new();
+        for step in &quote_response.route_plan {
+            let fee_mint = step.swap_info.fee_mint;
+            let total = lp_fees.entry(fee_mint).or_default();
+            *total = total
+                .checked_add(step.swap_info.fee_amount)
+                .ok_or(AnalysisError::FeeOverflow(fee_mint))?;
+        }
+        let lp_fees = lp_fees
+            .into_iter()
+            .map(|(mint, amount)| LpFee {
+                mint,
+                amount,
+                ui_amount: TokenAmount::of_mint(amount, &mint, decimals)
+                    .and_then(|amount| amount.to_decimal())
+                    .ok(),
+            })
+            .collect();
+
+        let platform_fee = match &quote_response.platform_fee {
+            Some(platform_fee) => {
+                let (mint, leg_amount) = match quote_response.swap_mode {
+                    SwapMode::ExactIn => (quote_response.output_mint, out_amount),
+                    SwapMode::ExactOut => (quote_response.input_mint, in_amount),
+                };
+                let ui_amount =
+                    TokenAmount::of_mint(platform_fee.amount, &mint, decimals)?.to_decimal()?;
+                Some(PlatformFeeShare {
+                    mint,
+                    amount: platform_fee.amount,
+                    ui_amount,
+                    fee_bps: platform_fee.fee_bps,
+                    share: ui_amount.checked_div(leg_amount).unwrap_or_default(),
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            input_mint: quote_response.input_mint,
+            output_mint: quote_response.output_mint,
+            swap_mode: quote_response.swap_mode.clone(),
+            in_amount,
+            out_amount,
+            effective_price,
+            worst_case_price,
+            lp_fees,
+            platform_fee,
+            price_impact_pct: quote_response.price_impact_pct,
+        })
+    }
+
+    /// Whether the absolute price impact is at most `max_price_impact_pct`, in the unit of `price_impact_pct`
+    pub fn is_price_impact_within(&self, max_price_impact_pct: Decimal) -> bool {
+        self.price_impact_pct.abs() <= max_price_impact_pct
+    }
+
+    pub fn check_price_impact(&self, max_price_impact_pct: Decimal) -> Result<(), AnalysisError> {
+        if self.is_price_impact_within(max_price_impact_pct) {
+            Ok(())
+        } else {
+            Err(AnalysisError::PriceImpactTooHigh {
+                price_impact_pct: self.price_impact_pct,
+                max_price_impact_pct,
+            })
+        }
+    }
+
+    /// Relative difference between the effective and the worst case price
+    pub fn worst_case_deviation(&self) -> Decimal {
+        (self.effective_price - self.worst_case_price)
+            .checked_div(self.effective_price)
+            .unwrap_or_default()
+    }
+}
+
+impl QuoteResponse {
+    pub fn analyze(&self, decimals: &HashMap<Pubkey, u8>) -> Result<QuoteAnalysis, AnalysisError> {
+        QuoteAnalysis::new(self, decimals)
+    }
+}
@@ -0,0 +1,125 @@
+//! Assembly of a v0 transaction from the swap instructions, resolving the address lookup tables on-chain
+//!
+
+use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    message::{v0, CompileError, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+use crate::{accounts::AccountFetcher, swap::SwapInstructionsResponse};
+
+#[derive(Debug, Error)]
+pub enum AssembleError {
+    #[error("Failed to fetch address lookup tables: {0}")]
+    Fetch(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Address lookup table {0} does not exist")]
+    LookupTableNotFound(Pubkey),
+    #[error("Failed to deserialize address lookup table {address}: {source}")]
+    InvalidLookupTable {
+        address: Pubkey,
+        #[source]
+        source: InstructionError,
+    },
+    #[error("Account fetcher returned {returned} accounts, {expected} were requested")]
+    AccountCountMismatch { expected: usize, returned: usize },
+    #[error("Failed to compile message: {0}")]
+    Compile(#[from] CompileError),
+}
+
+/// Builds unsigned v0 transactions out of [`SwapInstructionsResponse`]s
+#[derive(Clone, Debug)]
+pub struct TransactionAssembler<F> {
+    account_fetcher: F,
+}
+
+impl<F: AccountFetcher> TransactionAssembler<F> {
+    pub fn new(account_fetcher: F) -> Self {
+        Self { account_fetcher }
+    }
+
+    pub fn account_fetcher(&self) -> &F {
+        &self.account_fetcher
+    }
+
+    pub async fn fetch_address_lookup_tables(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, AssembleError> {
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let accounts = self
+            .account_fetcher
+            .get_multiple_accounts(addresses)
+            .await
+            .map_err(|error| AssembleError::Fetch(Box::new(error)))?;
+        if accounts.len() != addresses.len() {
+            return Err(AssembleError::AccountCountMismatch {
+                expected: addresses.len(),
+                returned: accounts.len(),
+            });
+        }
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(address, data)| {
+                let data = data.ok_or(AssembleError::LookupTableNotFound(*address))?;
+                let lookup_table = AddressLookupTable::deserialize(&data).map_err(|source| {
+                    AssembleError::InvalidLookupTable {
+                        address: *address,
+                        source,
+                    }
+                })?;
+                Ok(AddressLookupTableAccount {
+                    key: *address,
+                    addresses: lookup_table.addresses.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Unsigned transaction executing the swap instructions in order, compute budget, token ledger,
+    /// setup, swap, cleanup then other instructions
+    pub async fn assemble(
+        &self,
+        swap_instructions: &SwapInstructionsResponse,
+        payer: &Pubkey,
+        recent_blockhash: Hash,
+    ) -> Result<VersionedTransaction, AssembleError> {
+        let address_lookup_tables = self
+            .fetch_address_lookup_tables(&swap_instructions.address_lookup_table_addresses)
+            .await?;
+        let instructions = swap_instructions
+            .instructions()
+            .map(|(_, instruction)| instruction.clone())
+            .collect::<Vec<_>>();
+        compile_transaction(
+            payer,
+            &instructions,
+            &address_lookup_tables,
+            recent_blockhash,
+        )
+    }
+}
+
+/// Unsigned v0 transaction, the signatures are defaulted so that it can be signed in place
+pub fn compile_transaction(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    address_lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, AssembleError> {
+    let message =
+        v0::Message::try_compile(payer, instructions, address_lookup_tables, recent_blockhash)?;
+    let num_required_signatures = usize::from(message.header.num_required_signatures);
+    Ok(VersionedTransaction {
+        signatures: vec![Signature::default(); num_required_signatures],
+        message: VersionedMessage::V0(message),
+    })
+}
@@ -0,0 +1,241 @@
+//! Builder for [`JupiterSwapApiClient`]
+//!
+
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Client, Error, Proxy,
+};
+
+use crate::{
+    rate_limit::RateLimiter, retry::RetryPolicy, tokens::TokenCache, JupiterSwapApiClient,
+};
+
+pub const DEFAULT_QUOTE_PATH: &str = "/quote";
+pub const DEFAULT_SWAP_PATH: &str = "/swap";
+pub const DEFAULT_SWAP_INSTRUCTIONS_PATH: &str = "/swap-instructions";
+pub const DEFAULT_PROGRAM_ID_TO_LABEL_PATH: &str = "/program-id-to-label";
+pub const DEFAULT_TOKENS_BASE_PATH: &str = "https://lite-api.jup.ag/tokens/v2";
+pub const DEFAULT_PRICE_BASE_PATH: &str = "https://lite-api.jup.ag/price/v2";
+pub const DEFAULT_ULTRA_BASE_PATH: &str = "https://lite-api.jup.ag/ultra/v1";
+pub const DEFAULT_TRIGGER_BASE_PATH: &str = "https://lite-api.jup.ag/trigger/v1";
+pub const DEFAULT_RECURRING_BASE_PATH: &str = "https://lite-api.jup.ag/recurring/v1";
+
+#[derive(Debug, Default)]
+pub struct JupiterSwapApiClientBuilder {
+    base_path: String,
+    api_key: Option<String>,
+    default_headers: HeaderMap,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<Proxy>,
+    http_client: Option<Client>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    quote_path: Option<String>,
+    swap_path: Option<String>,
+    swap_instructions_path: Option<String>,
+    program_id_to_label_path: Option<String>,
+    tokens_base_path: Option<String>,
+    price_base_path: Option<String>,
+    ultra_base_path: Option<String>,
+    trigger_base_path: Option<String>,
+    recurring_base_path: Option<String>,
+}
+
+impl JupiterSwapApiClientBuilder {
+    pub fn new(base_path: impl Into<String>) -> Self {
+        Self {
+            base_path: base_path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// API key sent as the `x-api-key` header on every request
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Header sent on every request, ignored if a custom http client is provided
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Headers sent on every request, ignored if a custom http client is provided
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Ignored if a custom http client is provided
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Total timeout of a single request, from connecting until the response body has been read.
+    /// Ignored if a custom http client is provided
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Ignored if a custom http client is provided
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Ignored if a custom http client is provided
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Use a pre-built http client, the client level settings of this builder
+    /// (headers, timeouts, user agent and proxy) are then left to the provided client.
+    /// The api key is still sent on every request.
+    pub fn http_client(mut self, http_client: Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Retry policy of all requests, can be overridden with [`JupiterSwapApiClient::with_retry_policy`]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Make requests wait for a permit of the rate limiter instead of being rejected by the server
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Path of the quote endpoint relative to the base path, defaults to `/quote`
+    pub fn quote_path(mut self, quote_path: impl Into<String>) -> Self {
+        self.quote_path = Some(quote_path.into());
+        self
+    }
+
+    /// Path of the swap endpoint relative to the base path, defaults to `/swap`
+    pub fn swap_path(mut self, swap_path: impl Into<String>) -> Self {
+        self.swap_path = Some(swap_path.into());
+        self
+    }
+
+    /// Path of the swap instructions endpoint relative to the base path, defaults to `/swap-instructions`
+    pub fn swap_instructions_path(mut self, swap_instructions_path: impl Into<String>) -> Self {
+        self.swap_instructions_path = Some(swap_instructions_path.into());
+        self
+    }
+
+    /// Path of the program id to label endpoint relative to the base path, defaults to `/program-id-to-label`
+    pub fn program_id_to_label_path(mut self, program_id_to_label_path: impl Into<String>) -> Self {
+        self.program_id_to_label_path = Some(program_id_to_label_path.into());
+        self
+    }
+
+    /// Base path of the token API, defaults to `https://lite-api.jup.ag/tokens/v2`
+    pub fn tokens_base_path(mut self, tokens_base_path: impl Into<String>) -> Self {
+        self.tokens_base_path = Some(tokens_base_path.into());
+        self
+    }
+
+    /// Base path of the price API, defaults to `https://lite-api.jup.ag/price/v2`
+    pub fn price_base_path(mut self, price_base_path: impl Into<String>) -> Self {
+        self.price_base_path = Some(price_base_path.into());
+        self
+    }
+
+    /// Base path of the ultra API, defaults to `https://lite-api.jup.ag/ultra/v1`
+    pub fn ultra_base_path(mut self, ultra_base_path: impl Into<String>) -> Self {
+        self.ultra_base_path = Some(ultra_base_path.into());
+        self
+    }
+
+    /// Base path of the trigger API, defaults to `https://lite-api.jup.ag/trigger/v1`
+    pub fn trigger_base_path(mut self, trigger_base_path: impl Into<String>) -> Self {
+        self.trigger_base_path = Some(trigger_base_path.into());
+        self
+    }
+
+    /// Base path of the recurring API, defaults to `https://lite-api.jup.ag/recurring/v1`
+    pub fn recurring_base_path(mut self, recurring_base_path: impl Into<String>) -> Self {
+        self.recurring_base_path = Some(recurring_base_path.into());
+        self
+    }
+
+    pub fn build(self) -> Result<JupiterSwapApiClient, Error> {
+        let base_path = self.base_path.trim_end_matches('/').to_string();
+        let endpoint = |path: Option<String>, default: &str| {
+            format!("{}{}", base_path, path.as_deref().unwrap_or(default))
+        };
+        let quote_path = endpoint(self.quote_path, DEFAULT_QUOTE_PATH);
+        let swap_path = endpoint(self.swap_path, DEFAULT_SWAP_PATH);
+        let swap_instructions_path =
+            endpoint(self.swap_instructions_path, DEFAULT_SWAP_INSTRUCTIONS_PATH);
+        let program_id_to_label_path = endpoint(
+            self.program_id_to_label_path,
+            DEFAULT_PROGRAM_ID_TO_LABEL_PATH,
+        );
+        let other_api = |base_path: Option<String>, default: &str| {
+            base_path
+                .as_deref()
+                .unwrap_or(default)
+                .trim_end_matches('/')
+                .to_string()
+        };
+        let tokens_base_path = other_api(self.tokens_base_path, DEFAULT_TOKENS_BASE_PATH);
+        let price_base_path = other_api(self.price_base_path, DEFAULT_PRICE_BASE_PATH);
+        let ultra_base_path = other_api(self.ultra_base_path, DEFAULT_ULTRA_BASE_PATH);
+        let trigger_base_path = other_api(self.trigger_base_path, DEFAULT_TRIGGER_BASE_PATH);
+        let recurring_base_path = other_api(self.recurring_base_path, DEFAULT_RECURRING_BASE_PATH);
+
+        let http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => {
+                let mut builder = Client::builder()
+                    .http2_keep_alive_while_idle(true)
+                    .pool_idle_timeout(None)
+                    .http2_keep_alive_interval(Some(Duration::from_secs(10)))
+                    .default_headers(self.default_headers);
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(JupiterSwapApiClient {
+            base_path,
+            quote_path,
+            swap_path,
+            swap_instructions_path,
+            program_id_to_label_path,
+            tokens_base_path,
+            price_base_path,
+            ultra_base_path,
+            trigger_base_path,
+            recurring_base_path,
+            api_key: self.api_key,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            token_cache: TokenCache::default(),
+            http_client,
+        })
+    }
+}
@@ -0,0 +1,193 @@
+//! Composition of caller instructions with the swap instructions into a single transaction
+//!
+
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount, compute_budget, hash::Hash,
+    instruction::Instruction, pubkey::Pubkey, transaction::VersionedTransaction,
+};
+
+use crate::{
+    accounts::AccountFetcher,
+    assembler::{compile_transaction, AssembleError, TransactionAssembler},
+    estimate::TransactionEstimate,
+    swap::SwapInstructionsResponse,
+};
+
+/// Swap instructions with caller instructions inserted around them, the final order is:
+/// compute budget, token ledger, before setup, setup, before swap, swap, after swap, cleanup,
+/// after cleanup and other instructions.
+///
+/// Compute budget instructions are moved to the front with a single instruction of each kind,
+/// the ones provided by the caller override the ones of the swap.
+#[derive(Clone, Debug)]
+pub struct ComposedSwap<'a> {
+    swap_instructions: &'a SwapInstructionsResponse,
+    compute_budget_instructions: Vec<Instruction>,
+    before_setup: Vec<Instruction>,
+    before_swap: Vec<Instruction>,
+    after_swap: Vec<Instruction>,
+    after_cleanup: Vec<Instruction>,
+    address_lookup_table_addresses: Vec<Pubkey>,
+    address_lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+/// Compiled composed swap, unsigned
+#[derive(Clone, Debug)]
+pub struct ComposedTransaction {
+    pub transaction: VersionedTransaction,
+    pub estimate: TransactionEstimate,
+}
+
+impl ComposedTransaction {
+    pub fn fits_in_packet(&self) -> bool {
+        self.estimate.fits_in_packet()
+    }
+}
+
+impl<'a> ComposedSwap<'a> {
+    pub fn new(swap_instructions: &'a SwapInstructionsResponse) -> Self {
+        Self {
+            swap_instructions,
+            compute_budget_instructions: Vec::new(),
+            before_setup: Vec::new(),
+            before_swap: Vec::new(),
+            after_swap: Vec::new(),
+            after_cleanup: Vec::new(),
+            address_lookup_table_addresses: swap_instructions
+                .address_lookup_table_addresses
+                .clone(),
+            address_lookup_tables: Vec::new(),
+        }
+    }
+
+    fn push(
+        mut self,
+        instruction: Instruction,
+        slot: fn(&mut Self) -> &mut Vec<Instruction>,
+    ) -> Self {
+        if instruction.program_id == compute_budget::id() {
+            self.compute_budget_instructions.push(instruction);
+        } else {
+            slot(&mut self).push(instruction);
+        }
+        self
+    }
+
+    /// After the token ledger instruction, such as a transfer increasing the input amount
+    pub fn before_setup(self, instruction: Instruction) -> Self {
+        self.push(instruction, |composed| &mut composed.before_setup)
+    }
+
+    pub fn before_swap(self, instruction: Instruction) -> Self {
+        self.push(instruction, |composed| &mut composed.before_swap)
+    }
+
+    pub fn after_swap(self, instruction: Instruction) -> Self {
+        self.push(instruction, |composed| &mut composed.after_swap)
+    }
+
+    pub fn after_cleanup(self, instruction: Instruction) -> Self {
+        self.push(instruction, |composed| &mut composed.after_cleanup)
+    }
+
+    /// Address lookup table to fetch on assembly
+    pub fn address_lookup_table(mut self, address: Pubkey) -> Self {
+        if !self.address_lookup_table_addresses.contains(&address) {
+            self.address_lookup_table_addresses.push(address);
+        }
+        self
+    }
+
+    /// Address lookup table already resolved by the caller, it is not fetched on assembly
+    pub fn address_lookup_table_account(mut self, account: AddressLookupTableAccount) -> Self {
+        self.address_lookup_table_addresses
+            .retain(|address| *address != account.key);
+        self.address_lookup_tables
+            .retain(|resolved| resolved.key != account.key);
+        self.address_lookup_tables.push(account);
+        self
+    }
+
+    /// Addresses of the lookup tables still to be resolved
+    pub fn address_lookup_table_addresses(&self) -> &[Pubkey] {
+        &self.address_lookup_table_addresses
+    }
+
+    /// Compute budget instructions with one instruction per kind, in first seen order
+    fn compute_budget_instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::<Instruction>::new();
+        for instruction in self
+            .swap_instructions
+            .compute_budget_instructions
+            .iter()
+            .chain(&self.compute_budget_instructions)
+        {
+            let kind = instruction.data.first();
+            match instructions
+                .iter_mut()
+                .find(|existing| kind.is_some() && existing.data.first() == kind)
+            {
+                Some(existing) => *existing = instruction.clone(),
+                None => instructions.push(instruction.clone()),
+            }
+        }
+        instructions
+    }
+
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let swap_instructions = self.swap_instructions;
+        let mut instructions = self.compute_budget_instructions();
+        instructions.extend(swap_instructions.token_ledger_instruction.iter().cloned());
+        instructions.extend(self.before_setup.iter().cloned());
+        instructions.extend(swap_instructions.setup_instructions.iter().cloned());
+        instructions.extend(self.before_swap.iter().cloned());
+        instructions.push(swap_instructions.swap_instruction.clone());
+        instructions.extend(self.after_swap.iter().cloned());
+        instructions.extend(swap_instructions.cleanup_instruction.iter().cloned());
+        instructions.extend(self.after_cleanup.iter().cloned());
+        instructions.extend(swap_instructions.other_instructions.iter().cloned());
+        instructions
+    }
+
+    /// Compile with `address_lookup_tables` resolving the remaining addresses, on top of the resolved ones
+    pub fn compile(
+        &self,
+        payer: &Pubkey,
+        recent_blockhash: Hash,
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<ComposedTransaction, AssembleError> {
+        let mut tables = self.address_lookup_tables.clone();
+        for table in address_lookup_tables {
+            if !tables.iter().any(|resolved| resolved.key == table.key) {
+                tables.push(table.clone());
+            }
+        }
+        let transaction =
+            compile_transaction(payer, &self.instructions(), &tables, recent_blockhash)?;
+        Ok(ComposedTransaction {
+            estimate: TransactionEstimate::from_transaction(&transaction),
+            transaction,
+        })
+    }
+}
+
+impl SwapInstructionsResponse {
+    pub fn compose(&self) -> ComposedSwap<'_> {
+        ComposedSwap::new(self)
+    }
+}
+
+impl<F: AccountFetcher> TransactionAssembler<F> {
+    /// Same as [`TransactionAssembler::assemble`] for swap instructions composed with caller instructions
+    pub async fn assemble_composed(
+        &self,
+        composed_swap: &ComposedSwap<'_>,
+        payer: &Pubkey,
+        recent_blockhash: Hash,
+    ) -> Result<ComposedTransaction, AssembleError> {
+        let address_lookup_tables = self
+            .fetch_address_lookup_tables(composed_swap.address_lookup_table_addresses())
+            .await?;
+        composed_swap.compile(payer, recent_blockhash, &address_lookup_tables)
+    }
+}
@@ -0,0 +1,246 @@
+//! Dex labels used to include or exclude dexes from routing
+//!
+
+use std::{
+    collections::{btree_set, BTreeSet, HashMap},
+    convert::Infallible,
+    fmt,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+macro_rules! dex_labels {
+    ($($variant:ident => $label:literal,)*) => {
+        /// Label of a dex as returned by `/program-id-to-label`
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum DexLabel {
+            $($variant,)*
+            /// Label not known by this version of the client
+            Other(String),
+        }
+
+        impl DexLabel {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $label,)*
+                    Self::Other(label) => label,
+                }
+            }
+        }
+
+        impl From<&str> for DexLabel {
+            fn from(label: &str) -> Self {
+                match label {
+                    $($label => Self::$variant,)*
+                    _ => Self::Other(label.to_string()),
+                }
+            }
+        }
+    };
+}
+
+dex_labels! {
+    Aldrin => "Aldrin",
+    AldrinV2 => "Aldrin V2",
+    Bonkswap => "Bonkswap",
+    Crema => "Crema",
+    Fluxbeam => "FluxBeam",
+    GooseFxGamma => "GooseFX GAMMA",
+    Invariant => "Invariant",
+    LifinityV2 => "Lifinity V2",
+    Mercurial => "Mercurial",
+    Meteora => "Meteora",
+    MeteoraDammV2 => "Meteora DAMM v2",
+    MeteoraDlmm => "Meteora DLMM",
+    Moonit => "Moonit",
+    ObricV2 => "Obric V2",
+    OpenBookV2 => "OpenBook V2",
+    OrcaV1 => "Orca V1",
+    OrcaV2 => "Orca V2",
+    Penguin => "Penguin",
+    Perps => "Perps",
+    Phoenix => "Phoenix",
+    PumpFun => "Pump.fun",
+    PumpFunAmm => "Pump.fun Amm",
+    Raydium => "Raydium",
+    RaydiumClmm => "Raydium CLMM",
+    RaydiumCp => "Raydium CP",
+    RaydiumLaunchlab => "Raydium Launchlab",
+    Saber => "Saber",
+    SaberDecimals => "Saber (Decimals)",
+    Sanctum => "Sanctum",
+    SanctumInfinity => "Sanctum Infinity",
+    Saros => "Saros",
+    SolFi => "SolFi",
+    StabbleStableSwap => "Stabble Stable Swap",
+    StabbleWeightedSwap => "Stabble Weighted Swap",
+    TokenSwap => "Token Swap",
+    Whirlpool => "Whirlpool",
+    ZeroFi => "ZeroFi",
+}
+
+impl From<String> for DexLabel {
+    fn from(label: String) -> Self {
+        match DexLabel::from(label.as_str()) {
+            DexLabel::Other(_) => DexLabel::Other(label),
+            known => known,
+        }
+    }
+}
+
+impl FromStr for DexLabel {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl fmt::Display for DexLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Ordered set of dex labels, serialized as the comma delimited list expected by
+/// the `dexes` and `excludeDexes` quote parameters
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DexSet(BTreeSet<DexLabel>);
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Dexes not supported by the API: {0}")]
+pub struct UnsupportedDexes(pub DexSet);
+
+impl DexSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set of the labels supported by the API, from the `/program-id-to-label` response
+    pub fn from_program_id_to_label(program_id_to_label: &HashMap<Pubkey, String>) -> Self {
+        program_id_to_label
+            .values()
+            .map(|label| DexLabel::from(label.as_str()))
+            .collect()
+    }
+
+    pub fn insert(&mut self, label: DexLabel) -> bool {
+        self.0.insert(label)
+    }
+
+    pub fn remove(&mut self, label: &DexLabel) -> bool {
+        self.0.remove(label)
+    }
+
+    pub fn contains(&self, label: &DexLabel) -> bool {
+        self.0.contains(label)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_set::Iter<'_, DexLabel> {
+        self.0.iter()
+    }
+
+    pub fn union(&self, other: &DexSet) -> DexSet {
+        self.0.union(&other.0).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &DexSet) -> DexSet {
+        self.0.intersection(&other.0).cloned().collect()
+    }
+
+    pub fn difference(&self, other: &DexSet) -> DexSet {
+        self.0.difference(&other.0).cloned().collect()
+    }
+
+    pub fn is_disjoint(&self, other: &DexSet) -> bool {
+        self.0.is_disjoint(&other.0)
+    }
+
+    /// Check that every label of the set is in the supported set
+    pub fn validate(&self, supported: &DexSet) -> Result<(), UnsupportedDexes> {
+        let unsupported = self.difference(supported);
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(UnsupportedDexes(unsupported))
+        }
+    }
+}
+
+impl FromIterator<DexLabel> for DexSet {
+    fn from_iter<I: IntoIterator<Item = DexLabel>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<DexLabel> for DexSet {
+    fn extend<I: IntoIterator<Item = DexLabel>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for DexSet {
+    type Item = DexLabel;
+    type IntoIter = btree_set::IntoIter<DexLabel>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a DexSet {
+    type Item = &'a DexLabel;
+    type IntoIter = btree_set::Iter<'a, DexLabel>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for DexSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, label) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(label.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DexSet {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.split(',')
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(DexLabel::from)
+            .collect())
+    }
+}
+
+impl Serialize for DexSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DexSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let dexes = String::deserialize(deserializer)?;
+        Ok(dexes.parse().unwrap_or_default())
+    }
+}
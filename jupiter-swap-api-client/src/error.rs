@@ -0,0 +1,166 @@
+//! Errors returned by the client
+//!
+
+use std::{fmt, time::Duration};
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::quote::QuoteRequestError;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Request timed out: {0}")]
+    Timeout(#[source] reqwest::Error),
+    /// Connection, DNS, TLS or any other failure to exchange with the server
+    #[error("Request failed: {0}")]
+    Transport(#[source] reqwest::Error),
+    #[error("Rate limited{}: {body}", retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default())]
+    RateLimited {
+        retry_after: Option<Duration>,
+        error: Option<JupiterApiError>,
+        body: String,
+    },
+    #[error("Request failed with status {status}: {body}")]
+    RequestFailed {
+        status: StatusCode,
+        /// Error returned by the API, if the body could be parsed
+        error: Option<JupiterApiError>,
+        body: String,
+    },
+    #[error("Failed to serialize query: {0}")]
+    QuerySerializationError(#[from] serde_qs::Error),
+    #[error("Invalid quote request: {0}")]
+    InvalidQuoteRequest(#[from] QuoteRequestError),
+    #[error("Failed to deserialize response at {path}: {source}")]
+    DeserializationError {
+        /// Path of the field that failed to deserialize
+        path: String,
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout(error)
+        } else {
+            Self::Transport(error)
+        }
+    }
+}
+
+impl ClientError {
+    /// Whether sending the same request again might succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout(_) | Self::RateLimited { .. } => true,
+            Self::Transport(error) => error.is_connect(),
+            Self::RequestFailed { status, .. } => status.is_server_error(),
+            Self::QuerySerializationError(_)
+            | Self::InvalidQuoteRequest(_)
+            | Self::DeserializationError { .. } => false,
+        }
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Timeout(error) | Self::Transport(error) => error.status(),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::RequestFailed { status, .. } => Some(*status),
+            Self::QuerySerializationError(_)
+            | Self::InvalidQuoteRequest(_)
+            | Self::DeserializationError { .. } => None,
+        }
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Error returned by the API
+    pub fn api_error(&self) -> Option<&JupiterApiError> {
+        match self {
+            Self::RateLimited { error, .. } | Self::RequestFailed { error, .. } => error.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn error_code(&self) -> Option<&ErrorCode> {
+        self.api_error()?.error_code.as_ref()
+    }
+}
+
+/// Error body returned by the API
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterApiError {
+    pub error: String,
+    #[serde(default)]
+    pub error_code: Option<ErrorCode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum ErrorCode {
+    CouldNotFindAnyRoute,
+    NoRoutesFound,
+    TokenNotTradable,
+    CircularArbitrageIsDisabled,
+    RoutePlanDoesNotConsumeAllTheAmount,
+    MarketNotFound,
+    Other(String),
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::CouldNotFindAnyRoute => "COULD_NOT_FIND_ANY_ROUTE",
+            Self::NoRoutesFound => "NO_ROUTES_FOUND",
+            Self::TokenNotTradable => "TOKEN_NOT_TRADABLE",
+            Self::CircularArbitrageIsDisabled => "CIRCULAR_ARBITRAGE_IS_DISABLED",
+            Self::RoutePlanDoesNotConsumeAllTheAmount => {
+                "ROUTE_PLAN_DOES_NOT_CONSUME_ALL_THE_AMOUNT"
+            }
+            Self::MarketNotFound => "MARKET_NOT_FOUND",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "COULD_NOT_FIND_ANY_ROUTE" => Self::CouldNotFindAnyRoute,
+            "NO_ROUTES_FOUND" => Self::NoRoutesFound,
+            "TOKEN_NOT_TRADABLE" => Self::TokenNotTradable,
+            "CIRCULAR_ARBITRAGE_IS_DISABLED" => Self::CircularArbitrageIsDisabled,
+            "ROUTE_PLAN_DOES_NOT_CONSUME_ALL_THE_AMOUNT" => {
+                Self::RoutePlanDoesNotConsumeAllTheAmount
+            }
+            "MARKET_NOT_FOUND" => Self::MarketNotFound,
+            _ => Self::Other(code),
+        }
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::Other(code) => code,
+            code => code.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,100 @@
+//! Exact size and account counts of a transaction, to check it fits before signing and sending it
+//!
+
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount, hash::Hash, instruction::Instruction,
+    message::VersionedMessage, packet::PACKET_DATA_SIZE, pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+
+use crate::{
+    assembler::{compile_transaction, AssembleError},
+    swap::SwapInstructionsResponse,
+};
+
+/// Max number of accounts a transaction can lock, static and loaded from lookup tables
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionEstimate {
+    /// Size of the signed transaction
+    pub serialized_size: usize,
+    pub num_signatures: usize,
+    /// Accounts in the message account keys, 32 bytes each
+    pub static_accounts: usize,
+    /// Accounts loaded from address lookup tables, 1 byte each
+    pub lookup_table_accounts: usize,
+    pub lookup_tables: usize,
+}
+
+impl TransactionEstimate {
+    /// Estimate of the transaction compiled from the instructions, the size does not depend on the blockhash
+    pub fn new(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<Self, AssembleError> {
+        let transaction =
+            compile_transaction(payer, instructions, address_lookup_tables, Hash::default())?;
+        Ok(Self::from_transaction(&transaction))
+    }
+
+    pub fn from_transaction(transaction: &VersionedTransaction) -> Self {
+        let (lookup_table_accounts, lookup_tables) = match &transaction.message {
+            VersionedMessage::Legacy(_) => (0, 0),
+            VersionedMessage::V0(message) => (
+                message
+                    .address_table_lookups
+                    .iter()
+                    .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                    .sum(),
+                message.address_table_lookups.len(),
+            ),
+        };
+        Self {
+            serialized_size: bincode::serialized_size(transaction)
+                .map(|size| size as usize)
+                .unwrap_or(usize::MAX),
+            num_signatures: usize::from(transaction.message.header().num_required_signatures),
+            static_accounts: transaction.message.static_account_keys().len(),
+            lookup_table_accounts,
+            lookup_tables,
+        }
+    }
+
+    /// Unique accounts of the transaction, the count bounded by `max_accounts` when quoting
+    pub fn unique_accounts(&self) -> usize {
+        self.static_accounts + self.lookup_table_accounts
+    }
+
+    pub fn fits_in_packet(&self) -> bool {
+        self.serialized_size <= PACKET_DATA_SIZE
+    }
+
+    /// Bytes to save for the transaction to fit, 0 when it fits
+    pub fn bytes_over_limit(&self) -> usize {
+        self.serialized_size.saturating_sub(PACKET_DATA_SIZE)
+    }
+
+    pub fn exceeds_account_limit(&self) -> bool {
+        self.unique_accounts() > MAX_TRANSACTION_ACCOUNTS
+    }
+}
+
+impl SwapInstructionsResponse {
+    /// Estimate of the swap transaction along with `extra_instructions`, their position does not change the estimate.
+    /// Lookup tables not in `address_lookup_tables` are not used.
+    pub fn estimate(
+        &self,
+        payer: &Pubkey,
+        extra_instructions: &[Instruction],
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<TransactionEstimate, AssembleError> {
+        let instructions = self
+            .instructions()
+            .map(|(_, instruction)| instruction.clone())
+            .chain(extra_instructions.iter().cloned())
+            .collect::<Vec<_>>();
+        TransactionEstimate::new(payer, &instructions, address_lookup_tables)
+    }
+}
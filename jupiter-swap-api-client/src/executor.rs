@@ -0,0 +1,301 @@
+//! Sending of a signed swap transaction until it lands, fails or its blockhash expires
+//!
+
+use std::{future::Future, time::Duration};
+
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use thiserror::Error;
+use tokio::time::Instant;
+
+use crate::verify::JUPITER_PROGRAM_ID;
+
+/// Custom error of the Jupiter program when the out amount is below the min out amount
+pub const SLIPPAGE_TOLERANCE_EXCEEDED: u32 = 6001;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfirmationLevel {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureStatus {
+    pub slot: u64,
+    pub confirmation_level: ConfirmationLevel,
+    pub error: Option<TransactionError>,
+}
+
+/// RPC calls needed by the executor, mock it to test without a cluster
+pub trait ExecutorRpc {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Send without preflight nor retries from the RPC node, the executor rebroadcasts itself
+    fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = Result<Signature, Self::Error>> + Send;
+
+    /// None while the transaction has not been processed
+    fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> impl Future<Output = Result<Option<SignatureStatus>, Self::Error>> + Send;
+
+    fn get_block_height(&self) -> impl Future<Output = Result<u64, Self::Error>> + Send;
+}
+
+#[cfg(feature = "solana-client")]
+mod rpc {
+    use solana_client::{
+        client_error::ClientError, nonblocking::rpc_client::RpcClient,
+        rpc_config::RpcSendTransactionConfig,
+    };
+    use solana_sdk::{
+        commitment_config::CommitmentConfig, signature::Signature,
+        transaction::VersionedTransaction,
+    };
+
+    use super::{ConfirmationLevel, ExecutorRpc, SignatureStatus};
+
+    impl ExecutorRpc for RpcClient {
+        type Error = ClientError;
+
+        async fn send_transaction(
+            &self,
+            transaction: &VersionedTransaction,
+        ) -> Result<Signature, Self::Error> {
+            let config = RpcSendTransactionConfig {
+                skip_preflight: true,
+                max_retries: Some(0),
+                ..RpcSendTransactionConfig::default()
+            };
+            self.send_transaction_with_config(transaction, config).await
+        }
+
+        async fn get_signature_status(
+            &self,
+            signature: &Signature,
+        ) -> Result<Option<SignatureStatus>, Self::Error> {
+            let status = self
+                .get_signature_statuses(&[*signature])
+                .await?
+                .value
+                .into_iter()
+                .next()
+                .flatten();
+            Ok(status.map(|status| {
+                let confirmation_level =
+                    if status.satisfies_commitment(CommitmentConfig::finalized()) {
+                        ConfirmationLevel::Finalized
+                    } else if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                        ConfirmationLevel::Confirmed
+                    } else {
+                        ConfirmationLevel::Processed
+                    };
+                SignatureStatus {
+                    slot: status.slot,
+                    confirmation_level,
+                    error: status.err,
+                }
+            }))
+        }
+
+        async fn get_block_height(&self) -> Result<u64, Self::Error> {
+            RpcClient::get_block_height(self).await
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutorConfig {
+    /// Time between two sends of the transaction
+    pub rebroadcast_interval: Duration,
+    /// Time between two signature status and block height checks
+    pub poll_interval: Duration,
+    /// Level at which the transaction is considered landed
+    pub confirmation_level: ConfirmationLevel,
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self {
+            rebroadcast_interval: Duration::from_secs(2),
+            poll_interval: Duration::from_millis(500),
+            confirmation_level: ConfirmationLevel::Confirmed,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ExecutorError {
+    #[error("Transaction is not signed")]
+    MissingSignature,
+    #[error("RPC call failed: {0}")]
+    Rpc(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// Instruction which made the transaction fail
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailedInstruction {
+    pub index: u8,
+    /// None when the program is loaded from a lookup table, which programs cannot be
+    pub program_id: Option<Pubkey>,
+    pub error: InstructionError,
+}
+
+impl FailedInstruction {
+    pub fn is_slippage_exceeded(&self) -> bool {
+        self.program_id == Some(JUPITER_PROGRAM_ID)
+            && self.error == InstructionError::Custom(SLIPPAGE_TOLERANCE_EXCEEDED)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    Landed {
+        signature: Signature,
+        slot: u64,
+        confirmation_level: ConfirmationLevel,
+    },
+    Failed {
+        signature: Signature,
+        slot: u64,
+        error: TransactionError,
+        /// Set when the error is an instruction error
+        failed_instruction: Option<FailedInstruction>,
+    },
+    /// The block height went past the last valid block height without the transaction landing
+    Expired {
+        signature: Signature,
+        last_valid_block_height: u64,
+    },
+}
+
+impl ExecutionOutcome {
+    pub fn signature(&self) -> &Signature {
+        match self {
+            Self::Landed { signature, .. }
+            | Self::Failed { signature, .. }
+            | Self::Expired { signature, .. } => signature,
+        }
+    }
+
+    pub fn is_landed(&self) -> bool {
+        matches!(self, Self::Landed { .. })
+    }
+}
+
+/// Sends signed transactions and follows them until they land, fail or expire
+#[derive(Clone, Debug)]
+pub struct TransactionExecutor<R> {
+    rpc: R,
+    config: ExecutorConfig,
+}
+
+impl<R: ExecutorRpc> TransactionExecutor<R> {
+    pub fn new(rpc: R) -> Self {
+        Self::with_config(rpc, ExecutorConfig::default())
+    }
+
+    pub fn with_config(rpc: R, config: ExecutorConfig) -> Self {
+        Self { rpc, config }
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    /// Send the transaction, rebroadcasting it until it reaches the confirmation level, fails,
+    /// or `last_valid_block_height` is exceeded such as the one of [`crate::swap::SwapResponse`].
+    /// Errors of rebroadcasts are ignored, the first send has to succeed.
+    pub async fn execute(
+        &self,
+        transaction: &VersionedTransaction,
+        last_valid_block_height: u64,
+    ) -> Result<ExecutionOutcome, ExecutorError> {
+        let signature = match transaction.signatures.first() {
+            Some(signature) if *signature != Signature::default() => *signature,
+            _ => return Err(ExecutorError::MissingSignature),
+        };
+        let rpc_error = |error: R::Error| ExecutorError::Rpc(Box::new(error));
+
+        self.rpc
+            .send_transaction(transaction)
+            .await
+            .map_err(rpc_error)?;
+        let mut last_send = Instant::now();
+
+        loop {
+            tokio::time::sleep(self.config.poll_interval).await;
+
+            // Block height first so that a transaction landing in between is not reported as expired
+            let block_height = self.rpc.get_block_height().await.map_err(rpc_error)?;
+            if let Some(status) = self
+                .rpc
+                .get_signature_status(&signature)
+                .await
+                .map_err(rpc_error)?
+            {
+                if let Some(error) = status.error {
+                    return Ok(ExecutionOutcome::Failed {
+                        signature,
+                        slot: status.slot,
+                        failed_instruction: failed_instruction(transaction, &error),
+                        error,
+                    });
+                }
+                if status.confirmation_level >= self.config.confirmation_level {
+                    return Ok(ExecutionOutcome::Landed {
+                        signature,
+                        slot: status.slot,
+                        confirmation_level: status.confirmation_level,
+                    });
+                }
+                // Processed, no need to rebroadcast while waiting for the confirmation level
+                continue;
+            }
+
+            if block_height > last_valid_block_height {
+                return Ok(ExecutionOutcome::Expired {
+                    signature,
+                    last_valid_block_height,
+                });
+            }
+            if last_send.elapsed() >= self.config.rebroadcast_interval {
+                let _ = self.rpc.send_transaction(transaction).await;
+                last_send = Instant::now();
+            }
+        }
+    }
+}
+
+fn failed_instruction(
+    transaction: &VersionedTransaction,
+    error: &TransactionError,
+) -> Option<FailedInstruction> {
+    let TransactionError::InstructionError(index, error) = error else {
+        return None;
+    };
+    let program_id = transaction
+        .message
+        .instructions()
+        .get(usize::from(*index))
+        .and_then(|instruction| {
+            transaction
+                .message
+                .static_account_keys()
+                .get(usize::from(instruction.program_id_index))
+        })
+        .copied();
+    Some(FailedInstruction {
+        index: *index,
+        program_id,
+        error: error.clone(),
+    })
+}
@@ -0,0 +1,138 @@
+//! Registry resolving program ids to dex labels from `/program-id-to-label`
+//!
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use tokio::time::Instant;
+
+use crate::{
+    dexes::DexSet,
+    route_plan_with_metadata::RoutePlanStep,
+    serde_helpers::field_as_string,
+    swap::{SwapInstructionKind, SwapInstructionsResponse},
+    ClientError, JupiterSwapApiClient,
+};
+
+#[derive(Deserialize, PartialEq, Eq, Hash)]
+pub(crate) struct ProgramIdInternal(#[serde(with = "field_as_string")] pub(crate) Pubkey);
+
+#[derive(Debug, Clone, Default)]
+pub struct LabelRegistry {
+    labels: HashMap<Pubkey, String>,
+    program_ids: HashMap<String, Vec<Pubkey>>,
+}
+
+/// Instruction of a [`SwapInstructionsResponse`] with the label of the program it targets
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabeledInstruction<'a> {
+    pub kind: SwapInstructionKind,
+    pub instruction: &'a Instruction,
+    /// None when the program is not a dex known by the API, e.g. the compute budget or token programs
+    pub label: Option<&'a str>,
+}
+
+impl LabelRegistry {
+    pub fn new(program_id_to_label: HashMap<Pubkey, String>) -> Self {
+        let mut program_ids: HashMap<String, Vec<Pubkey>> = HashMap::new();
+        for (program_id, label) in &program_id_to_label {
+            program_ids
+                .entry(label.clone())
+                .or_default()
+                .push(*program_id);
+        }
+        for program_ids in program_ids.values_mut() {
+            program_ids.sort();
+        }
+        Self {
+            labels: program_id_to_label,
+            program_ids,
+        }
+    }
+
+    pub async fn fetch(client: &JupiterSwapApiClient) -> Result<Self, ClientError> {
+        Ok(Self::new(client.program_id_to_label().await?))
+    }
+
+    pub fn label(&self, program_id: &Pubkey) -> Option<&str> {
+        self.labels.get(program_id).map(String::as_str)
+    }
+
+    /// Program ids of a dex, a label can be shared by several program versions
+    pub fn program_ids(&self, label: &str) -> &[Pubkey] {
+        self.program_ids
+            .get(label)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Dexes supported by the API
+    pub fn dexes(&self) -> DexSet {
+        DexSet::from_program_id_to_label(&self.labels)
+    }
+
+    pub fn label_instructions<'a>(
+        &'a self,
+        swap_instructions: &'a SwapInstructionsResponse,
+    ) -> Vec<LabeledInstruction<'a>> {
+        swap_instructions
+            .instructions()
+            .map(|(kind, instruction)| LabeledInstruction {
+                kind,
+                instruction,
+                label: self.label(&instruction.program_id),
+            })
+            .collect()
+    }
+
+    /// Program ids of the dex used by the route plan step, resolved from its label
+    pub fn step_program_ids(&self, step: &RoutePlanStep) -> &[Pubkey] {
+        self.program_ids(&step.swap_info.label)
+    }
+}
+
+type Cache = Option<(Instant, Arc<LabelRegistry>)>;
+
+/// [`LabelRegistry`] fetched lazily and refreshed once older than the ttl, clones share the cache
+#[derive(Clone)]
+pub struct CachedLabelRegistry {
+    client: JupiterSwapApiClient,
+    ttl: Duration,
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl CachedLabelRegistry {
+    pub fn new(client: JupiterSwapApiClient, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            cache: Arc::default(),
+        }
+    }
+
+    pub async fn get(&self) -> Result<Arc<LabelRegistry>, ClientError> {
+        if let Some((fetched_at, registry)) = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+        {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(registry.clone());
+            }
+        }
+        self.refresh().await
+    }
+
+    pub async fn refresh(&self) -> Result<Arc<LabelRegistry>, ClientError> {
+        let registry = Arc::new(LabelRegistry::fetch(&self.client).await?);
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((Instant::now(), registry.clone()));
+        Ok(registry)
+    }
+}
@@ -1,16 +1,48 @@
-use quote::{InternalQuoteRequest, QuoteRequest, QuoteResponse};
-use reqwest::{Client, Error, Response};
+use quote::{QuoteRequest, QuoteResponse};
+use error::JupiterApiError;
+use label_registry::ProgramIdInternal;
+use solana_sdk::pubkey::Pubkey;
+use reqwest::{Client, Error, Method, RequestBuilder, Response, StatusCode};
+use rate_limit::RateLimiter;
+use retry::RetryPolicy;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-use std::time::Duration;
+use tokens::TokenCache;
 use swap::{SwapInstructionsResponse, SwapInstructionsResponseInternal, SwapRequest, SwapResponse};
-use thiserror::Error;
 
+pub use builder::JupiterSwapApiClientBuilder;
+pub use error::ClientError;
+
+pub mod accounts;
+pub mod amount;
+pub mod analysis;
+pub mod assembler;
+pub mod builder;
+pub mod compose;
+pub mod dexes;
+pub mod error;
+pub mod estimate;
+pub mod executor;
+pub mod label_registry;
+pub mod price;
 pub mod quote;
+pub mod quote_type;
+pub mod rate_limit;
+pub mod recurring;
+pub mod render;
+pub mod retry;
+pub mod route_graph;
 pub mod route_plan_with_metadata;
 pub mod serde_helpers;
+#[cfg(feature = "simulate")]
+pub mod simulate;
 pub mod swap;
+pub mod tokens;
+pub mod transaction;
 pub mod transaction_config;
+pub mod trigger;
+pub mod ultra;
+pub mod verify;
 
 #[derive(Clone)]
 pub struct JupiterSwapApiClient {
@@ -18,69 +50,169 @@ pub struct JupiterSwapApiClient {
     pub quote_path: String,
     pub swap_path: String,
     pub swap_instructions_path: String,
+    pub program_id_to_label_path: String,
+    /// Base path of the token API, which is served separately from the swap API
+    pub tokens_base_path: String,
+    /// Base path of the price API
+    pub price_base_path: String,
+    /// Base path of the ultra API
+    pub ultra_base_path: String,
+    /// Base path of the trigger API
+    pub trigger_base_path: String,
+    /// Base path of the recurring API
+    pub recurring_base_path: String,
+    /// Sent as the `x-api-key` header on every request
+    pub api_key: Option<String>,
+    pub retry_policy: RetryPolicy,
+    /// Shared by the clones of the client
+    pub rate_limiter: Option<RateLimiter>,
+    /// Shared by the clones of the client
+    pub token_cache: TokenCache,
     pub http_client: Client,
 }
 
-#[derive(Debug, Error)]
-pub enum ClientError {
-    #[error("Request failed with status {status}: {body}")]
-    RequestFailed {
-        status: reqwest::StatusCode,
-        body: String,
-    },
-    #[error("Failed to deserialize response: {0}")]
-    DeserializationError(#[from] reqwest::Error),
+/// Endpoints of the API
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Quote,
+    Swap,
+    SwapInstructions,
+    ProgramIdToLabel,
+    Tokens,
+    Price,
+    UltraOrder,
+    UltraExecute,
+    TriggerCreateOrder,
+    TriggerCancelOrder,
+    TriggerOrders,
+    RecurringCreateOrder,
+    RecurringCancelOrder,
+    RecurringDeposit,
+    RecurringWithdraw,
+    RecurringOrders,
+}
+
+impl Endpoint {
+    /// Whether sending the same request several times is harmless
+    pub fn is_idempotent(&self) -> bool {
+        match self {
+            Self::Quote
+            | Self::SwapInstructions
+            | Self::ProgramIdToLabel
+            | Self::Tokens
+            | Self::Price
+            | Self::UltraOrder
+            | Self::TriggerOrders
+            | Self::RecurringOrders => true,
+            Self::Swap
+            | Self::UltraExecute
+            | Self::TriggerCreateOrder
+            | Self::TriggerCancelOrder
+            | Self::RecurringCreateOrder
+            | Self::RecurringCancelOrder
+            | Self::RecurringDeposit
+            | Self::RecurringWithdraw => false,
+        }
+    }
 }
 
 async fn check_is_success(response: Response) -> Result<Response, ClientError> {
     if !response.status().is_success() {
         let status = response.status();
+        let retry_after = retry::retry_after(response.headers());
         let body = response.text().await.unwrap_or_default();
-        return Err(ClientError::RequestFailed { status, body });
+        let error = serde_json::from_str::<JupiterApiError>(&body).ok();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(ClientError::RateLimited {
+                retry_after,
+                error,
+                body,
+            });
+        }
+        return Err(ClientError::RequestFailed {
+            status,
+            error,
+            body,
+        });
     }
     Ok(response)
 }
 
-async fn check_status_code_and_deserialize<T: DeserializeOwned>(
-    response: Response,
-) -> Result<T, ClientError> {
-    let response = check_is_success(response).await?;
-    response
-        .json::<T>()
-        .await
-        .map_err(ClientError::DeserializationError)
+async fn deserialize_response<T: DeserializeOwned>(response: Response) -> Result<T, ClientError> {
+    let body = response.text().await?;
+    let deserializer = &mut serde_json::Deserializer::from_str(&body);
+    serde_path_to_error::deserialize(deserializer).map_err(|error| {
+        let path = error.path().to_string();
+        ClientError::DeserializationError {
+            path,
+            source: error.into_inner(),
+            body,
+        }
+    })
 }
 
 impl JupiterSwapApiClient {
     pub fn new(base_path: String) -> Result<Self, Error> {
-        let quote_path = format!("{}/quote", base_path);
-        let swap_path = format!("{}/swap", base_path);
-        let swap_instructions_path = format!("{}/swap-instructions", base_path);
-        let http_client = Client::builder()
-            .http2_keep_alive_while_idle(true)
-            .pool_idle_timeout(None)
-            .http2_keep_alive_interval(Some(Duration::from_secs(10)))
-            .build()?;
-        Ok(Self {
-            base_path,
-            quote_path,
-            swap_path,
-            swap_instructions_path,
-            http_client,
-        })
+        Self::builder(base_path).build()
     }
 
-    pub async fn quote(&self, mut quote_request: QuoteRequest) -> Result<QuoteResponse, ClientError> {
-        let url = &self.quote_path;
-        let extra_args = quote_request.quote_args.take();
-        let internal_quote_request = InternalQuoteRequest::from(quote_request);
-        let response = self.http_client
-            .get(url)
-            .query(&internal_quote_request)
-            .query(&extra_args)
-            .send()
-            .await?;
-        check_status_code_and_deserialize(response).await
+    pub fn builder(base_path: impl Into<String>) -> JupiterSwapApiClientBuilder {
+        JupiterSwapApiClientBuilder::new(base_path)
+    }
+
+    /// Clone of the client using a different retry policy, to override the policy for some calls
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy,
+            ..self.clone()
+        }
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self.http_client.request(method, url);
+        match &self.api_key {
+            Some(api_key) => request.header("x-api-key", api_key),
+            None => request,
+        }
+    }
+
+    /// Send the request and check its status, retrying according to the retry policy
+    /// and waiting for the rate limiter before each attempt
+    async fn send(
+        &self,
+        endpoint: Endpoint,
+        request: RequestBuilder,
+    ) -> Result<Response, ClientError> {
+        let max_attempts = self.retry_policy.max_attempts_for(endpoint);
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(endpoint).await;
+            }
+            let Some(attempt_request) = request.try_clone() else {
+                return check_is_success(request.send().await?).await;
+            };
+            let result = match attempt_request.send().await {
+                Ok(response) => check_is_success(response).await,
+                Err(error) => Err(error.into()),
+            };
+            let error = match result {
+                Ok(response) => return Ok(response),
+                Err(error) if attempt < max_attempts && self.retry_policy.should_retry(&error) => {
+                    error
+                }
+                Err(error) => return Err(error),
+            };
+            tokio::time::sleep(self.retry_policy.backoff(attempt, error.retry_after())).await;
+            attempt += 1;
+        }
+    }
+
+    pub async fn quote(&self, quote_request: QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        let url = format!("{}?{}", self.quote_path, quote_request.to_query_string()?);
+        let request = self.request(Method::GET, &url);
+        let response = self.send(Endpoint::Quote, request).await?;
+        deserialize_response(response).await
     }
 
     pub async fn swap(
@@ -88,26 +220,38 @@ impl JupiterSwapApiClient {
         swap_request: &SwapRequest,
         extra_args: Option<HashMap<String, String>>,
     ) -> Result<SwapResponse, ClientError> {
-        let response = self.http_client
-            .post(&self.swap_path)
+        let request = self
+            .request(Method::POST, &self.swap_path)
             .query(&extra_args)
-            .json(swap_request)
-            .send()
-            .await?;
-        check_status_code_and_deserialize(response).await
+            .json(swap_request);
+        let response = self.send(Endpoint::Swap, request).await?;
+        deserialize_response(response).await
     }
 
     pub async fn swap_instructions(
         &self,
         swap_request: &SwapRequest,
     ) -> Result<SwapInstructionsResponse, ClientError> {
-        let response = self.http_client
-            .post(&self.swap_instructions_path)
-            .json(swap_request)
-            .send()
-            .await?;
-        check_status_code_and_deserialize::<SwapInstructionsResponseInternal>(response)
+        let request = self
+            .request(Method::POST, &self.swap_instructions_path)
+            .json(swap_request);
+        let response = self.send(Endpoint::SwapInstructions, request).await?;
+        deserialize_response::<SwapInstructionsResponseInternal>(response)
             .await
             .map(Into::into)
     }
+
+    /// Labels of the dexes supported by the API, keyed by program id
+    pub async fn program_id_to_label(&self) -> Result<HashMap<Pubkey, String>, ClientError> {
+        let request = self.request(Method::GET, &self.program_id_to_label_path);
+        let response = self.send(Endpoint::ProgramIdToLabel, request).await?;
+        deserialize_response::<HashMap<ProgramIdInternal, String>>(response)
+            .await
+            .map(|labels| {
+                labels
+                    .into_iter()
+                    .map(|(program_id, label)| (program_id.0, label))
+                    .collect()
+            })
+    }
 }
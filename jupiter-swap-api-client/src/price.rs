@@ -0,0 +1,171 @@
+//! Price API for USD valuation of mints and quotes
+//!
+
+use std::collections::HashMap;
+
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    amount::TokenAmount,
+    deserialize_response,
+    quote::QuoteResponse,
+    serde_helpers::{field_as_string, option_field_as_string},
+    ClientError, Endpoint, JupiterSwapApiClient,
+};
+
+/// Max number of mints per price request
+pub const MAX_IDS_PER_REQUEST: usize = 100;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceEntry {
+    #[serde(with = "field_as_string")]
+    pub id: Pubkey,
+    /// How the price was computed, e.g. `derivedPrice` or `buyPrice`
+    #[serde(rename = "type")]
+    pub price_type: String,
+    /// Price in USD of one whole token
+    #[serde(with = "field_as_string")]
+    pub price: Decimal,
+    #[serde(default)]
+    pub extra_info: Option<PriceExtraInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceExtraInfo {
+    #[serde(default)]
+    pub confidence_level: Option<ConfidenceLevel>,
+    #[serde(default)]
+    pub last_swapped_price: Option<LastSwappedPrice>,
+    #[serde(default)]
+    pub quoted_price: Option<QuotedPrice>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfidenceLevel {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSwappedPrice {
+    #[serde(default)]
+    pub last_jupiter_sell_at: Option<i64>,
+    #[serde(default, with = "option_field_as_string")]
+    pub last_jupiter_sell_price: Option<Decimal>,
+    #[serde(default)]
+    pub last_jupiter_buy_at: Option<i64>,
+    #[serde(default, with = "option_field_as_string")]
+    pub last_jupiter_buy_price: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotedPrice {
+    #[serde(default, with = "option_field_as_string")]
+    pub buy_price: Option<Decimal>,
+    #[serde(default)]
+    pub buy_at: Option<i64>,
+    #[serde(default, with = "option_field_as_string")]
+    pub sell_price: Option<Decimal>,
+    #[serde(default)]
+    pub sell_at: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PriceResponseInternal {
+    /// Mints without a price are null
+    data: HashMap<String, Option<PriceEntry>>,
+}
+
+/// USD value of both legs of a quote, None when the mint has no price
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteUsdValue {
+    pub in_usd: Option<Decimal>,
+    pub out_usd: Option<Decimal>,
+}
+
+impl QuoteUsdValue {
+    pub fn new(
+        quote_response: &QuoteResponse,
+        prices: &HashMap<Pubkey, PriceEntry>,
+        decimals: &HashMap<Pubkey, u8>,
+    ) -> Self {
+        let usd_value = |mint: &Pubkey, amount: u64| {
+            let price = prices.get(mint)?.price;
+            TokenAmount::of_mint(amount, mint, decimals)
+                .and_then(|amount| amount.to_decimal())
+                .ok()?
+                .checked_mul(price)
+        };
+        Self {
+            in_usd: usd_value(&quote_response.input_mint, quote_response.in_amount),
+            out_usd: usd_value(&quote_response.output_mint, quote_response.out_amount),
+        }
+    }
+
+    /// Value lost between the input and output legs, positive when the output is worth less
+    pub fn usd_difference(&self) -> Option<Decimal> {
+        self.in_usd?.checked_sub(self.out_usd?)
+    }
+}
+
+impl JupiterSwapApiClient {
+    /// USD prices of the mints, batched over the per request limit.
+    /// Mints without a price are missing from the result.
+    pub async fn prices(
+        &self,
+        mints: &[Pubkey],
+    ) -> Result<HashMap<Pubkey, PriceEntry>, ClientError> {
+        let mut mints = mints.to_vec();
+        mints.sort();
+        mints.dedup();
+
+        let mut prices = HashMap::with_capacity(mints.len());
+        for chunk in mints.chunks(MAX_IDS_PER_REQUEST) {
+            let ids = chunk
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let request = self
+                .request(Method::GET, &self.price_base_path)
+                .query(&[("ids", ids.as_str()), ("showExtraInfo", "true")]);
+            let response = self.send(Endpoint::Price, request).await?;
+            let price_response: PriceResponseInternal = deserialize_response(response).await?;
+            prices.extend(
+                price_response
+                    .data
+                    .into_values()
+                    .flatten()
+                    .map(|price| (price.id, price)),
+            );
+        }
+        Ok(prices)
+    }
+
+    /// USD value of both legs of the quote, decimals are resolved through the token API
+    pub async fn quote_usd_value(
+        &self,
+        quote_response: &QuoteResponse,
+    ) -> Result<QuoteUsdValue, ClientError> {
+        let mints = [quote_response.input_mint, quote_response.output_mint];
+        let prices = self.prices(&mints).await?;
+        let decimals = self
+            .token_infos(&mints)
+            .await?
+            .into_iter()
+            .map(|(mint, token_info)| (mint, token_info.decimals))
+            .collect();
+        Ok(QuoteUsdValue::new(quote_response, &prices, &decimals))
+    }
+}
@@ -1,14 +1,21 @@
 //! Quote data structure for quoting and quote response
 //!
 
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::BTreeSet, str::FromStr};
 
+use crate::dexes::DexSet;
+use crate::quote_type::{QuoteArgs, QuoteType, RoutingConstraints};
+use crate::ClientError;
 use crate::route_plan_with_metadata::RoutePlanWithMetadata;
 use crate::serde_helpers::field_as_string;
 use anyhow::{anyhow, Error};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+/// Max slippage, 100%
+pub const MAX_SLIPPAGE_BPS: u16 = 10_000;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -53,6 +60,7 @@ impl FromStr for SwapMode {
 }
 
 #[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct ComputeUnitScore {
     pub max_penalty_bps: Option<f64>,
 }
@@ -86,8 +94,11 @@ pub struct QuoteRequest {
     pub minimize_slippage: Option<bool>,
     /// Platform fee in basis points
     pub platform_fee_bps: Option<u8>,
-    pub dexes: Option<Dexes>,
-    pub excluded_dexes: Option<Dexes>,
+    /// Restrict routing to these dexes
+    pub dexes: Option<DexSet>,
+    /// Exclude these dexes from routing
+    #[serde(rename = "excludeDexes")]
+    pub excluded_dexes: Option<DexSet>,
     /// Quote only direct routes
     pub only_direct_routes: Option<bool>,
     /// Quote fit into legacy transaction
@@ -100,97 +111,261 @@ pub struct QuoteRequest {
     /// The max is an estimation and not the exact count
     pub max_accounts: Option<usize>,
     /// Quote type to be used for routing, switches the algorithm
-    pub quote_type: Option<String>,
+    pub quote_type: Option<QuoteType>,
     /// Extra args which are quote type specific to allow controlling settings from the top level
-    pub quote_args: Option<HashMap<String, String>>,
+    #[serde(flatten)]
+    pub quote_args: Option<QuoteArgs>,
     /// enable only full liquid markets as intermediate tokens
     pub prefer_liquid_dexes: Option<bool>,
-    /// Use the compute unit score to pick a route
+    /// Use the compute unit score to pick a route, flattened to `maxPenaltyBps`
+    #[serde(flatten)]
     pub compute_unit_score: Option<ComputeUnitScore>,
     /// Routing constraints
-    pub routing_constraints: Option<String>,
+    pub routing_constraints: Option<RoutingConstraints>,
     /// Token category based intermediates token
     pub token_category_based_intermediate_tokens: Option<bool>,
 }
 
-// Essentially the same as QuoteRequest, but without the extra args
-// as we pass the extra args separately
-#[derive(Serialize, Debug, Default, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct InternalQuoteRequest {
-    #[serde(with = "field_as_string")]
-    pub input_mint: Pubkey,
-    #[serde(with = "field_as_string")]
-    pub output_mint: Pubkey,
-    /// The amount to swap, have to factor in the token decimals.
-    #[serde(with = "field_as_string")]
-    pub amount: u64,
-    /// (ExactIn or ExactOut) Defaults to ExactIn.
-    /// ExactOut is for supporting use cases where you need an exact token amount, like payments.
-    /// In this case the slippage is on the input token.
-    pub swap_mode: Option<SwapMode>,
-    /// Allowed slippage in basis points
-    pub slippage_bps: u16,
-    /// Default is false.
-    /// By setting this to true, our API will suggest smart slippage info that you can use.
-    /// slippageBps is what we suggest you to use. Additionally, you should check out max_auto_slippage_bps and auto_slippage_collision_usd_value.
-    pub auto_slippage: Option<bool>,
-    /// The max amount of slippage in basis points that you are willing to accept for auto slippage.
-    pub max_auto_slippage_bps: Option<u16>,
-    pub compute_auto_slippage: bool,
-    /// The max amount of USD value that you are willing to accept for auto slippage.
-    pub auto_slippage_collision_usd_value: Option<u32>,
-    /// Quote with a greater amount to find the route to minimize slippage
-    pub minimize_slippage: Option<bool>,
-    /// Platform fee in basis points
-    pub platform_fee_bps: Option<u8>,
-    pub dexes: Option<Dexes>,
-    pub excluded_dexes: Option<Dexes>,
-    /// Quote only direct routes
-    pub only_direct_routes: Option<bool>,
-    /// Quote fit into legacy transaction
-    pub as_legacy_transaction: Option<bool>,
-    /// Restrict intermediate tokens to a top token set that has stable liquidity.
-    /// This will help to ease potential high slippage error rate when swapping with minimal impact on pricing.
-    pub restrict_intermediate_tokens: Option<bool>,
-    /// Find a route given a maximum number of accounts involved,
-    /// this might dangerously limit routing ending up giving a bad price.
-    /// The max is an estimation and not the exact count
-    pub max_accounts: Option<usize>,
-    // Quote type to be used for routing, switches the algorithm
-    pub quote_type: Option<String>,
-    // enable only full liquid markets as intermediate tokens
-    pub prefer_liquid_dexes: Option<bool>,
-}
+impl QuoteRequest {
+    pub fn builder() -> QuoteRequestBuilder {
+        QuoteRequestBuilder::default()
+    }
+
+    /// Query string sent to `/quote`, the quote args are flattened next to the other parameters
+    pub fn to_query_string(&self) -> Result<String, ClientError> {
+        self.check_quote_args()?;
+        Ok(serde_qs::to_string(self)?)
+    }
 
-impl From<QuoteRequest> for InternalQuoteRequest {
-    fn from(request: QuoteRequest) -> Self {
-        InternalQuoteRequest {
-            input_mint: request.input_mint,
-            output_mint: request.output_mint,
-            amount: request.amount,
-            swap_mode: request.swap_mode,
-            slippage_bps: request.slippage_bps,
-            auto_slippage: request.auto_slippage,
-            max_auto_slippage_bps: request.max_auto_slippage_bps,
-            compute_auto_slippage: request.compute_auto_slippage,
-            auto_slippage_collision_usd_value: request.auto_slippage_collision_usd_value,
-            minimize_slippage: request.minimize_slippage,
-            platform_fee_bps: request.platform_fee_bps,
-            dexes: request.dexes,
-            excluded_dexes: request.excluded_dexes,
-            only_direct_routes: request.only_direct_routes,
-            as_legacy_transaction: request.as_legacy_transaction,
-            restrict_intermediate_tokens: request.restrict_intermediate_tokens,
-            max_accounts: request.max_accounts,
-            quote_type: request.quote_type,
-            prefer_liquid_dexes: request.prefer_liquid_dexes,
+    /// Args not modeled by the client must not duplicate a named parameter once flattened
+    fn check_quote_args(&self) -> Result<(), QuoteRequestError> {
+        let Some(quote_args) = &self.quote_args else {
+            return Ok(());
+        };
+        if quote_args.is_empty() {
+            return Ok(());
         }
+        let named_parameters = named_parameters();
+        match quote_args
+            .args
+            .keys()
+            .find(|key| named_parameters.contains(key.as_str()))
+        {
+            Some(key) => Err(QuoteRequestError::QuoteArgCollision(key.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Names of the query parameters of the fields of [`QuoteRequest`]
+fn named_parameters() -> BTreeSet<String> {
+    let request = QuoteRequest {
+        compute_unit_score: Some(ComputeUnitScore::default()),
+        ..QuoteRequest::default()
+    };
+    match serde_json::to_value(request) {
+        Ok(serde_json::Value::Object(parameters)) => parameters.keys().cloned().collect(),
+        _ => BTreeSet::new(),
     }
 }
 
-/// Comma delimited list of dex labels
-type Dexes = String;
+#[derive(Debug, Error, PartialEq)]
+pub enum QuoteRequestError {
+    #[error("Input mint is required")]
+    MissingInputMint,
+    #[error("Output mint is required")]
+    MissingOutputMint,
+    #[error("Input and output mints are both {0}")]
+    SameMints(Pubkey),
+    #[error("Amount must be greater than 0")]
+    ZeroAmount,
+    #[error("Slippage of {0} bps is above {MAX_SLIPPAGE_BPS} bps")]
+    SlippageTooHigh(u16),
+    #[error("Max auto slippage of {0} bps is above {MAX_SLIPPAGE_BPS} bps")]
+    MaxAutoSlippageTooHigh(u16),
+    #[error("Max auto slippage is set without enabling auto slippage")]
+    MaxAutoSlippageWithoutAutoSlippage,
+    #[error("Dexes are both included and excluded: {0}")]
+    OverlappingDexes(DexSet),
+    #[error("Quote arg {0} is already a parameter of the request")]
+    QuoteArgCollision(String),
+}
+
+/// Builder of a [`QuoteRequest`] checking its invariants on build
+#[derive(Debug, Default, Clone)]
+pub struct QuoteRequestBuilder {
+    input_mint: Option<Pubkey>,
+    output_mint: Option<Pubkey>,
+    request: QuoteRequest,
+}
+
+impl QuoteRequestBuilder {
+    pub fn input_mint(mut self, input_mint: Pubkey) -> Self {
+        self.input_mint = Some(input_mint);
+        self
+    }
+
+    pub fn output_mint(mut self, output_mint: Pubkey) -> Self {
+        self.output_mint = Some(output_mint);
+        self
+    }
+
+    /// The amount to swap, have to factor in the token decimals.
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.request.amount = amount;
+        self
+    }
+
+    pub fn swap_mode(mut self, swap_mode: SwapMode) -> Self {
+        self.request.swap_mode = Some(swap_mode);
+        self
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.request.slippage_bps = slippage_bps;
+        self
+    }
+
+    pub fn auto_slippage(mut self, auto_slippage: bool) -> Self {
+        self.request.auto_slippage = Some(auto_slippage);
+        self
+    }
+
+    /// Requires auto slippage to be enabled
+    pub fn max_auto_slippage_bps(mut self, max_auto_slippage_bps: u16) -> Self {
+        self.request.max_auto_slippage_bps = Some(max_auto_slippage_bps);
+        self
+    }
+
+    pub fn compute_auto_slippage(mut self, compute_auto_slippage: bool) -> Self {
+        self.request.compute_auto_slippage = compute_auto_slippage;
+        self
+    }
+
+    pub fn auto_slippage_collision_usd_value(mut self, usd_value: u32) -> Self {
+        self.request.auto_slippage_collision_usd_value = Some(usd_value);
+        self
+    }
+
+    pub fn minimize_slippage(mut self, minimize_slippage: bool) -> Self {
+        self.request.minimize_slippage = Some(minimize_slippage);
+        self
+    }
+
+    pub fn platform_fee_bps(mut self, platform_fee_bps: u8) -> Self {
+        self.request.platform_fee_bps = Some(platform_fee_bps);
+        self
+    }
+
+    pub fn dexes(mut self, dexes: DexSet) -> Self {
+        self.request.dexes = Some(dexes);
+        self
+    }
+
+    pub fn excluded_dexes(mut self, excluded_dexes: DexSet) -> Self {
+        self.request.excluded_dexes = Some(excluded_dexes);
+        self
+    }
+
+    pub fn only_direct_routes(mut self, only_direct_routes: bool) -> Self {
+        self.request.only_direct_routes = Some(only_direct_routes);
+        self
+    }
+
+    pub fn as_legacy_transaction(mut self, as_legacy_transaction: bool) -> Self {
+        self.request.as_legacy_transaction = Some(as_legacy_transaction);
+        self
+    }
+
+    pub fn restrict_intermediate_tokens(mut self, restrict_intermediate_tokens: bool) -> Self {
+        self.request.restrict_intermediate_tokens = Some(restrict_intermediate_tokens);
+        self
+    }
+
+    pub fn max_accounts(mut self, max_accounts: usize) -> Self {
+        self.request.max_accounts = Some(max_accounts);
+        self
+    }
+
+    pub fn quote_type(mut self, quote_type: QuoteType) -> Self {
+        self.request.quote_type = Some(quote_type);
+        self
+    }
+
+    /// Quote type specific arg, it must not be the name of another parameter
+    pub fn quote_arg(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.request
+            .quote_args
+            .get_or_insert_with(QuoteArgs::new)
+            .args
+            .insert(key.into(), value.to_string());
+        self
+    }
+
+    pub fn quote_args(mut self, quote_args: QuoteArgs) -> Self {
+        self.request.quote_args = Some(quote_args);
+        self
+    }
+
+    pub fn prefer_liquid_dexes(mut self, prefer_liquid_dexes: bool) -> Self {
+        self.request.prefer_liquid_dexes = Some(prefer_liquid_dexes);
+        self
+    }
+
+    pub fn compute_unit_score(mut self, compute_unit_score: ComputeUnitScore) -> Self {
+        self.request.compute_unit_score = Some(compute_unit_score);
+        self
+    }
+
+    pub fn routing_constraints(
+        mut self,
+        routing_constraints: impl Into<RoutingConstraints>,
+    ) -> Self {
+        self.request.routing_constraints = Some(routing_constraints.into());
+        self
+    }
+
+    pub fn token_category_based_intermediate_tokens(mut self, enabled: bool) -> Self {
+        self.request.token_category_based_intermediate_tokens = Some(enabled);
+        self
+    }
+
+    pub fn build(self) -> Result<QuoteRequest, QuoteRequestError> {
+        let mut request = self.request;
+        request.input_mint = self.input_mint.ok_or(QuoteRequestError::MissingInputMint)?;
+        request.output_mint = self
+            .output_mint
+            .ok_or(QuoteRequestError::MissingOutputMint)?;
+
+        if request.input_mint == request.output_mint {
+            return Err(QuoteRequestError::SameMints(request.input_mint));
+        }
+        if request.amount == 0 {
+            return Err(QuoteRequestError::ZeroAmount);
+        }
+        if request.slippage_bps > MAX_SLIPPAGE_BPS {
+            return Err(QuoteRequestError::SlippageTooHigh(request.slippage_bps));
+        }
+        if let Some(max_auto_slippage_bps) = request.max_auto_slippage_bps {
+            if request.auto_slippage != Some(true) {
+                return Err(QuoteRequestError::MaxAutoSlippageWithoutAutoSlippage);
+            }
+            if max_auto_slippage_bps > MAX_SLIPPAGE_BPS {
+                return Err(QuoteRequestError::MaxAutoSlippageTooHigh(
+                    max_auto_slippage_bps,
+                ));
+            }
+        }
+        if let (Some(dexes), Some(excluded_dexes)) = (&request.dexes, &request.excluded_dexes) {
+            let overlap = dexes.intersection(excluded_dexes);
+            if !overlap.is_empty() {
+                return Err(QuoteRequestError::OverlappingDexes(overlap));
+            }
+        }
+        request.check_quote_args()?;
+        Ok(request)
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
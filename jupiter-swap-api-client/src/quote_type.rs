@@ -0,0 +1,142 @@
+//! Quote type, quote type specific args and routing constraints of a quote request
+//!
+
+use std::{collections::BTreeMap, convert::Infallible, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// Quote type to be used for routing, switches the algorithm
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum QuoteType {
+    Metis,
+    /// Quote type not known by this version of the client
+    Other(String),
+}
+
+impl QuoteType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Metis => "metis",
+            Self::Other(quote_type) => quote_type,
+        }
+    }
+}
+
+impl From<&str> for QuoteType {
+    fn from(quote_type: &str) -> Self {
+        match quote_type {
+            "metis" => Self::Metis,
+            _ => Self::Other(quote_type.to_string()),
+        }
+    }
+}
+
+impl FromStr for QuoteType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl fmt::Display for QuoteType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for QuoteType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for QuoteType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let quote_type = String::deserialize(deserializer)?;
+        Ok(Self::from(quote_type.as_str()))
+    }
+}
+
+/// Quote type specific args, sent as top level query parameters.
+/// Their keys must not be the name of another parameter of the request.
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuoteArgs {
+    #[serde(flatten)]
+    pub args: BTreeMap<String, String>,
+}
+
+impl QuoteArgs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.args.insert(key.into(), value.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+}
+
+/// Routing constraints sent as a JSON object
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RouteConstraints {
+    #[serde(flatten)]
+    pub constraints: Map<String, Value>,
+}
+
+impl RouteConstraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.constraints.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Routing constraints, sent in the `routingConstraints` query parameter
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoutingConstraints {
+    Constraints(RouteConstraints),
+    /// Sent verbatim, for formats not modeled by this client
+    Raw(String),
+}
+
+impl From<RouteConstraints> for RoutingConstraints {
+    fn from(constraints: RouteConstraints) -> Self {
+        Self::Constraints(constraints)
+    }
+}
+
+impl fmt::Display for RoutingConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constraints(constraints) => {
+                f.write_str(&serde_json::to_string(constraints).map_err(|_| fmt::Error)?)
+            }
+            Self::Raw(constraints) => f.write_str(constraints),
+        }
+    }
+}
+
+impl Serialize for RoutingConstraints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RoutingConstraints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let constraints = String::deserialize(deserializer)?;
+        Ok(match serde_json::from_str(&constraints) {
+            Ok(constraints) => Self::Constraints(constraints),
+            Err(_) => Self::Raw(constraints),
+        })
+    }
+}
@@ -0,0 +1,131 @@
+//! Client side token bucket rate limiting
+//!
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use thiserror::Error;
+use tokio::time::Instant;
+
+use crate::Endpoint;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq)]
+pub enum RateLimitError {
+    #[error("Requests per second must be finite and positive, got {0}")]
+    InvalidRate(f64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    requests_per_second: f64,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// `requests_per_second` must be finite and positive, a burst of 0 allows a single request at once
+    pub fn new(requests_per_second: f64, burst: u32) -> Result<Self, RateLimitError> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            return Err(RateLimitError::InvalidRate(requests_per_second));
+        }
+        Ok(Self {
+            requests_per_second,
+            burst: burst.max(1),
+        })
+    }
+
+    /// Limit expressed like the API tiers, e.g. 60 requests per minute for the free tier
+    pub fn per_minute(requests: u32) -> Result<Self, RateLimitError> {
+        Self::new(f64::from(requests) / 60.0, requests)
+    }
+
+    /// Sustained rate at which permits are refilled
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Max number of requests that can be sent at once after being idle
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate_limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit,
+            tokens: f64::from(rate_limit.burst),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a permit, or return how long to wait until one is available
+    fn try_acquire(&mut self, now: Instant) -> Option<Duration> {
+        let capacity = f64::from(self.rate_limit.burst);
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate_limit.requests_per_second)
+            .min(capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let missing = 1.0 - self.tokens;
+            // Very low rates can exceed the max duration
+            Some(
+                Duration::try_from_secs_f64(missing / self.rate_limit.requests_per_second)
+                    .unwrap_or(Duration::MAX),
+            )
+        }
+    }
+}
+
+/// Token bucket rate limiter, clones share the same buckets so that all tasks using
+/// clones of a [`crate::JupiterSwapApiClient`] cooperate
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+    endpoint_buckets: HashMap<Endpoint, Arc<Mutex<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Limiter with a single bucket shared by all endpoints
+    pub fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(TokenBucket::new(rate_limit))),
+            endpoint_buckets: HashMap::new(),
+        }
+    }
+
+    /// Give the endpoint its own bucket instead of the shared one
+    pub fn with_endpoint_limit(mut self, endpoint: Endpoint, rate_limit: RateLimit) -> Self {
+        self.endpoint_buckets
+            .insert(endpoint, Arc::new(Mutex::new(TokenBucket::new(rate_limit))));
+        self
+    }
+
+    /// Wait until a request to the endpoint is allowed
+    pub async fn acquire(&self, endpoint: Endpoint) {
+        let bucket = self.endpoint_buckets.get(&endpoint).unwrap_or(&self.bucket);
+        loop {
+            let wait = bucket
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .try_acquire(Instant::now());
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Recurring API for dollar cost averaging orders, either time based or price based
+//!
+
+use std::collections::HashMap;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    deserialize_response, serde_helpers::field_as_string, swap::base64_serialize_deserialize,
+    trigger::OrderStatusFilter, ClientError, Endpoint, JupiterSwapApiClient,
+};
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRecurringOrderRequest {
+    #[serde(with = "field_as_string")]
+    pub user: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    pub params: RecurringOrderParams,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum RecurringOrderParams {
+    Time(TimeBasedParams),
+    Price(PriceBasedParams),
+}
+
+/// Buy with a fixed amount on each cycle
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeBasedParams {
+    /// Total amount of input mint, split evenly across the orders, have to factor in the token decimals.
+    pub in_amount: u64,
+    pub number_of_orders: u64,
+    /// Seconds between two orders
+    pub interval: u64,
+    /// Skip the cycle when the price of the output mint is below
+    pub min_price: Option<f64>,
+    /// Skip the cycle when the price of the output mint is above
+    pub max_price: Option<f64>,
+    /// Unix timestamp in seconds of the first order, defaults to now
+    pub start_at: Option<i64>,
+}
+
+/// Buy so that the USDC value of the position increases by a fixed amount on each cycle
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceBasedParams {
+    /// Initial deposit of input mint, have to factor in the token decimals.
+    pub deposit_amount: u64,
+    /// USDC value increment per cycle, have to factor in the USDC decimals.
+    pub increment_usdc_value: u64,
+    /// Seconds between two cycles
+    pub interval: u64,
+    /// Unix timestamp in seconds of the first cycle, defaults to now
+    pub start_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RecurringType {
+    Time,
+    Price,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelRecurringOrderRequest {
+    #[serde(with = "field_as_string")]
+    pub order: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub user: Pubkey,
+    pub recurring_type: RecurringType,
+}
+
+/// Deposit more input mint into a price based order
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringDepositRequest {
+    #[serde(with = "field_as_string")]
+    pub order: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawMint {
+    In,
+    Out,
+}
+
+/// Withdraw from a price based order
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringWithdrawRequest {
+    #[serde(with = "field_as_string")]
+    pub order: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub user: Pubkey,
+    pub input_or_output: WithdrawMint,
+    /// Everything is withdrawn when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+}
+
+/// Unsigned transaction returned by the create, cancel, deposit and withdraw calls
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringTransactionResponse {
+    pub request_id: String,
+    #[serde(with = "base64_serialize_deserialize")]
+    pub transaction: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RecurringTypeFilter {
+    Time,
+    Price,
+    All,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringOrdersRequest {
+    #[serde(with = "field_as_string")]
+    pub user: Pubkey,
+    pub order_status: OrderStatusFilter,
+    pub recurring_type: RecurringTypeFilter,
+    pub include_failed_tx: bool,
+    /// Starts at 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringOrdersResponse {
+    #[serde(default)]
+    pub time: Vec<RecurringOrder>,
+    #[serde(default)]
+    pub price: Vec<RecurringOrder>,
+    #[serde(default)]
+    pub all: Vec<RecurringOrder>,
+    #[serde(default)]
+    pub total_pages: u32,
+    #[serde(default)]
+    pub page: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringOrder {
+    #[serde(with = "field_as_string")]
+    pub user_pubkey: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub order_key: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub open_tx: Option<String>,
+    #[serde(default)]
+    pub close_tx: Option<String>,
+    /// Fields specific to the time based or price based orders
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl JupiterSwapApiClient {
+    pub async fn create_recurring_order(
+        &self,
+        create_request: &CreateRecurringOrderRequest,
+    ) -> Result<RecurringTransactionResponse, ClientError> {
+        let url = format!("{}/createOrder", self.recurring_base_path);
+        let request = self.request(Method::POST, &url).json(create_request);
+        let response = self.send(Endpoint::RecurringCreateOrder, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn cancel_recurring_order(
+        &self,
+        cancel_request: &CancelRecurringOrderRequest,
+    ) -> Result<RecurringTransactionResponse, ClientError> {
+        let url = format!("{}/cancelOrder", self.recurring_base_path);
+        let request = self.request(Method::POST, &url).json(cancel_request);
+        let response = self.send(Endpoint::RecurringCancelOrder, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn recurring_deposit(
+        &self,
+        deposit_request: &RecurringDepositRequest,
+    ) -> Result<RecurringTransactionResponse, ClientError> {
+        let url = format!("{}/priceDeposit", self.recurring_base_path);
+        let request = self.request(Method::POST, &url).json(deposit_request);
+        let response = self.send(Endpoint::RecurringDeposit, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn recurring_withdraw(
+        &self,
+        withdraw_request: &RecurringWithdrawRequest,
+    ) -> Result<RecurringTransactionResponse, ClientError> {
+        let url = format!("{}/priceWithdraw", self.recurring_base_path);
+        let request = self.request(Method::POST, &url).json(withdraw_request);
+        let response = self.send(Endpoint::RecurringWithdraw, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn recurring_orders(
+        &self,
+        orders_request: &RecurringOrdersRequest,
+    ) -> Result<RecurringOrdersResponse, ClientError> {
+        let url = format!("{}/getRecurringOrders", self.recurring_base_path);
+        let request = self.request(Method::GET, &url).query(orders_request);
+        let response = self.send(Endpoint::RecurringOrders, request).await?;
+        deserialize_response(response).await
+    }
+}
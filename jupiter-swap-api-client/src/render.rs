@@ -0,0 +1,182 @@
+//! Human readable renderings of a route plan, as a text tree or a Graphviz DOT document.
+//! Both are `Display` so they can be formatted into logs directly, mints are shortened
+//! unless formatted with the alternate flag `{:#}`.
+//!
+
+use std::fmt;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    quote::QuoteResponse, route_graph::stage_steps, route_plan_with_metadata::RoutePlanStep,
+};
+
+/// Mint shortened to its first and last 4 characters unless `full`
+struct Mint<'a>(&'a Pubkey, bool);
+
+impl fmt::Display for Mint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mint = self.0.to_string();
+        if self.1 || mint.len() <= 8 {
+            f.write_str(&mint)
+        } else {
+            write!(f, "{}…{}", &mint[..4], &mint[mint.len() - 4..])
+        }
+    }
+}
+
+/// Text tree of the route, steps swapping the same mint are grouped under it
+#[derive(Clone, Copy, Debug)]
+pub struct RouteTree<'a> {
+    quote_response: Option<&'a QuoteResponse>,
+    route_plan: &'a [RoutePlanStep],
+}
+
+impl<'a> RouteTree<'a> {
+    pub fn new(route_plan: &'a [RoutePlanStep]) -> Self {
+        Self {
+            quote_response: None,
+            route_plan,
+        }
+    }
+}
+
+impl fmt::Display for RouteTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mint = |mint| Mint(mint, alternate);
+
+        if let Some(quote_response) = self.quote_response {
+            writeln!(
+                f,
+                "{} {} -> {} {} ({:?}, threshold {}, slippage {} bps, price impact {})",
+                quote_response.in_amount,
+                mint(&quote_response.input_mint),
+                quote_response.out_amount,
+                mint(&quote_response.output_mint),
+                quote_response.swap_mode,
+                quote_response.other_amount_threshold,
+                quote_response.slippage_bps,
+                quote_response.price_impact_pct,
+            )?;
+        }
+
+        let stages = stage_steps(self.route_plan).collect::<Vec<_>>();
+
+        for (stage_index, stage) in stages.iter().enumerate() {
+            let last_stage = stage_index + 1 == stages.len();
+            let (branch, indent) = if last_stage {
+                ("└─", "   ")
+            } else {
+                ("├─", "│  ")
+            };
+            writeln!(f, "{branch} {}", mint(&stage[0].swap_info.input_mint))?;
+            for (step_index, step) in stage.iter().enumerate() {
+                let branch = if step_index + 1 == stage.len() {
+                    "└─"
+                } else {
+                    "├─"
+                };
+                let swap_info = &step.swap_info;
+                writeln!(
+                    f,
+                    "{indent}{branch} {}% {} -> {}: in {}, out {}, fee {} {}",
+                    step.percent,
+                    swap_info.label,
+                    mint(&swap_info.output_mint),
+                    swap_info.in_amount,
+                    swap_info.out_amount,
+                    swap_info.fee_amount,
+                    mint(&swap_info.fee_mint),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Graphviz DOT document of the route with the mints as nodes and the AMMs as edges
+#[derive(Clone, Copy, Debug)]
+pub struct RouteDot<'a> {
+    quote_response: Option<&'a QuoteResponse>,
+    route_plan: &'a [RoutePlanStep],
+}
+
+impl<'a> RouteDot<'a> {
+    pub fn new(route_plan: &'a [RoutePlanStep]) -> Self {
+        Self {
+            quote_response: None,
+            route_plan,
+        }
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl fmt::Display for RouteDot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mint_label = |mint| Mint(mint, alternate);
+
+        writeln!(f, "digraph route {{")?;
+        writeln!(f, "  rankdir=LR;")?;
+        writeln!(f, "  node [shape=ellipse];")?;
+
+        let mut mints = Vec::new();
+        for step in self.route_plan {
+            for mint in [&step.swap_info.input_mint, &step.swap_info.output_mint] {
+                if !mints.contains(&mint) {
+                    mints.push(mint);
+                }
+            }
+        }
+        for mint in mints {
+            let is_end = self.quote_response.is_some_and(|quote_response| {
+                *mint == quote_response.input_mint || *mint == quote_response.output_mint
+            });
+            writeln!(
+                f,
+                "  \"{mint}\" [label=\"{}\"{}];",
+                mint_label(mint),
+                if is_end { ", shape=box" } else { "" }
+            )?;
+        }
+
+        for step in self.route_plan {
+            let swap_info = &step.swap_info;
+            writeln!(
+                f,
+                "  \"{}\" -> \"{}\" [label=\"{} {}%\\nin {} out {}\\nfee {} {}\"];",
+                swap_info.input_mint,
+                swap_info.output_mint,
+                escape(&swap_info.label),
+                step.percent,
+                swap_info.in_amount,
+                swap_info.out_amount,
+                swap_info.fee_amount,
+                mint_label(&swap_info.fee_mint),
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl QuoteResponse {
+    /// Text tree of the route plan headed by the quoted amounts
+    pub fn route_tree(&self) -> RouteTree<'_> {
+        RouteTree {
+            quote_response: Some(self),
+            route_plan: &self.route_plan,
+        }
+    }
+
+    /// Graphviz DOT document of the route plan, the input and output mints are boxes
+    pub fn route_dot(&self) -> RouteDot<'_> {
+        RouteDot {
+            quote_response: Some(self),
+            route_plan: &self.route_plan,
+        }
+    }
+}
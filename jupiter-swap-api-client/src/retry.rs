@@ -0,0 +1,119 @@
+//! Retry policy with exponential backoff for the API requests
+//!
+
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+
+use crate::{ClientError, Endpoint};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts including the first one, 1 disables retrying
+    pub max_attempts: u32,
+    /// Backoff before the first retry, doubled on each following retry
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Randomize each backoff between half and the full value to spread out retries of concurrent callers
+    pub jitter: bool,
+    /// Wait at least the duration of the `Retry-After` header when the server provides one
+    pub respect_retry_after: bool,
+    pub retryable_statuses: Vec<StatusCode>,
+    /// Retry on timeouts and connection errors
+    pub retry_transport_errors: bool,
+    /// Also retry endpoints that are not idempotent such as `/swap`
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: true,
+            respect_retry_after: true,
+            retryable_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_transport_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy sending each request exactly once
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Number of attempts allowed for a request to the endpoint
+    pub fn max_attempts_for(&self, endpoint: Endpoint) -> u32 {
+        if endpoint.is_idempotent() || self.retry_non_idempotent {
+            self.max_attempts.max(1)
+        } else {
+            1
+        }
+    }
+
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Whether the failed request should be sent again, the statuses are classified with `retryable_statuses`
+    /// while timeouts and connection errors follow [`ClientError::is_retryable`]
+    pub fn should_retry(&self, error: &ClientError) -> bool {
+        match error {
+            ClientError::Timeout(_) | ClientError::Transport(_) => {
+                self.retry_transport_errors && error.is_retryable()
+            }
+            ClientError::RateLimited { .. } => {
+                self.is_retryable_status(StatusCode::TOO_MANY_REQUESTS)
+            }
+            ClientError::RequestFailed { status, .. } => self.is_retryable_status(*status),
+            ClientError::QuerySerializationError(_)
+            | ClientError::InvalidQuoteRequest(_)
+            | ClientError::DeserializationError { .. } => false,
+        }
+    }
+
+    /// Delay before the retry following the failed `attempt`, starting at 1
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let mut backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        if self.jitter && !backoff.is_zero() {
+            backoff = rand::thread_rng().gen_range(backoff / 2..=backoff);
+        }
+        match retry_after {
+            Some(retry_after) if self.respect_retry_after => backoff.max(retry_after),
+            _ => backoff,
+        }
+    }
+}
+
+/// Parses the `Retry-After` header when expressed in seconds
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
@@ -0,0 +1,175 @@
+//! Graph view of a route plan, to inspect and validate a route before signing
+//!
+
+use std::collections::BTreeSet;
+
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::{
+    dexes::{DexLabel, DexSet},
+    quote::QuoteResponse,
+    route_plan_with_metadata::{RoutePlanStep, RoutePlanWithMetadata},
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RouteGraphError {
+    #[error("Route plan is empty")]
+    EmptyRoutePlan,
+    #[error("Splits of stage {stage} from {input_mint} sum to {total}%")]
+    InvalidSplit {
+        stage: usize,
+        input_mint: Pubkey,
+        total: u32,
+    },
+    #[error("Stage {stage} swaps {mint} which no previous stage produced")]
+    UnexpectedInputMint { stage: usize, mint: Pubkey },
+    #[error("Stage {stage} produces {mint} which was already swapped")]
+    Cycle { stage: usize, mint: Pubkey },
+    #[error("{0} is produced but never swapped into the output mint")]
+    UnconsumedMint(Pubkey),
+    #[error("Route never produces the output mint {0}")]
+    OutputMintNotReached(Pubkey),
+}
+
+/// Consecutive steps swapping the same input mint, the amount is split between them by percent
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteStage {
+    pub input_mint: Pubkey,
+    pub splits: Vec<RoutePlanStep>,
+}
+
+impl RouteStage {
+    pub fn output_mints(&self) -> BTreeSet<Pubkey> {
+        self.splits
+            .iter()
+            .map(|step| step.swap_info.output_mint)
+            .collect()
+    }
+}
+
+/// Consecutive steps of the route plan swapping the same input mint, not validated
+pub(crate) fn stage_steps(route_plan: &[RoutePlanStep]) -> impl Iterator<Item = &[RoutePlanStep]> {
+    route_plan.chunk_by(|step, next| step.swap_info.input_mint == next.swap_info.input_mint)
+}
+
+/// Validated route plan, the stages are in the order of the route plan
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteGraph {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub stages: Vec<RouteStage>,
+}
+
+impl RouteGraph {
+    pub fn new(
+        route_plan: &RoutePlanWithMetadata,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+    ) -> Result<Self, RouteGraphError> {
+        let stages = stage_steps(route_plan)
+            .map(|splits| RouteStage {
+                input_mint: splits[0].swap_info.input_mint,
+                splits: splits.to_vec(),
+            })
+            .collect::<Vec<_>>();
+        if stages.is_empty() {
+            return Err(RouteGraphError::EmptyRoutePlan);
+        }
+
+        // Mints produced and not swapped yet
+        let mut available = BTreeSet::from([input_mint]);
+        let mut consumed = BTreeSet::new();
+        for (index, stage) in stages.iter().enumerate() {
+            let total = stage
+                .splits
+                .iter()
+                .map(|step| u32::from(step.percent))
+                .sum::<u32>();
+            if total != 100 {
+                return Err(RouteGraphError::InvalidSplit {
+                    stage: index,
+                    input_mint: stage.input_mint,
+                    total,
+                });
+            }
+            if !available.remove(&stage.input_mint) {
+                return Err(RouteGraphError::UnexpectedInputMint {
+                    stage: index,
+                    mint: stage.input_mint,
+                });
+            }
+            consumed.insert(stage.input_mint);
+            for mint in stage.output_mints() {
+                if consumed.contains(&mint) {
+                    return Err(RouteGraphError::Cycle { stage: index, mint });
+                }
+                available.insert(mint);
+            }
+        }
+
+        if !available.remove(&output_mint) {
+            return Err(RouteGraphError::OutputMintNotReached(output_mint));
+        }
+        if let Some(mint) = available.into_iter().next() {
+            return Err(RouteGraphError::UnconsumedMint(mint));
+        }
+
+        Ok(Self {
+            input_mint,
+            output_mint,
+            stages,
+        })
+    }
+
+    /// Number of stages between the input and the output mint
+    pub fn hop_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_direct(&self) -> bool {
+        self.stages.len() == 1
+    }
+
+    /// Distinct mint to mint hops, in route order
+    pub fn hops(&self) -> Vec<(Pubkey, Pubkey)> {
+        let mut hops = Vec::new();
+        for stage in &self.stages {
+            for output_mint in stage.output_mints() {
+                let hop = (stage.input_mint, output_mint);
+                if !hops.contains(&hop) {
+                    hops.push(hop);
+                }
+            }
+        }
+        hops
+    }
+
+    /// Mints the route goes through, other than the input and output mints
+    pub fn intermediate_mints(&self) -> BTreeSet<Pubkey> {
+        self.steps()
+            .flat_map(|step| [step.swap_info.input_mint, step.swap_info.output_mint])
+            .filter(|mint| *mint != self.input_mint && *mint != self.output_mint)
+            .collect()
+    }
+
+    pub fn amm_keys(&self) -> BTreeSet<Pubkey> {
+        self.steps().map(|step| step.swap_info.amm_key).collect()
+    }
+
+    pub fn dexes(&self) -> DexSet {
+        self.steps()
+            .map(|step| DexLabel::from(step.swap_info.label.as_str()))
+            .collect()
+    }
+
+    pub fn steps(&self) -> impl Iterator<Item = &RoutePlanStep> {
+        self.stages.iter().flat_map(|stage| stage.splits.iter())
+    }
+}
+
+impl QuoteResponse {
+    pub fn route_graph(&self) -> Result<RouteGraph, RouteGraphError> {
+        RouteGraph::new(&self.route_plan, self.input_mint, self.output_mint)
+    }
+}
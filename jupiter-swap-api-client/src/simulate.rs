@@ -0,0 +1,368 @@
+//! Local simulation of a swap transaction, previewing the balance changes of the user.
+//! Requires the `simulate` feature.
+//!
+
+use std::{collections::HashMap, convert::Infallible, future::Future};
+
+use solana_sdk::{
+    pubkey::Pubkey,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use thiserror::Error;
+
+use crate::{
+    quote::{QuoteResponse, SwapMode},
+    verify::{associated_token_address, NATIVE_MINT, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+};
+
+/// Offset of the amount in a token account, after the mint and the owner
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountState {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulationOutput {
+    pub error: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// State after the transaction of the accounts requested, in the same order
+    pub accounts: Vec<Option<AccountState>>,
+}
+
+/// Runs simulations, typically an RPC node
+pub trait SimulationBackend {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Current state of the accounts, None when the account does not exist
+    fn get_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> impl Future<Output = Result<Vec<Option<AccountState>>, Self::Error>> + Send;
+
+    /// Simulate the transaction without verifying its signatures, returning the state of `pubkeys` after it.
+    /// As with `simulateTransaction`, the accounts are all None when the simulation fails and None for
+    /// the accounts not loaded by the transaction
+    fn simulate(
+        &self,
+        transaction: &VersionedTransaction,
+        pubkeys: &[Pubkey],
+    ) -> impl Future<Output = Result<SimulationOutput, Self::Error>> + Send;
+}
+
+/// Backend returning fixed account states, for tests. The accounts after the simulation are None
+/// when it fails or when they are not static accounts of the transaction, as with an RPC node
+#[derive(Clone, Debug, Default)]
+pub struct FakeSimulationBackend {
+    pub accounts_before: HashMap<Pubkey, AccountState>,
+    pub accounts_after: HashMap<Pubkey, AccountState>,
+    pub error: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationBackend for FakeSimulationBackend {
+    type Error = Infallible;
+
+    async fn get_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<AccountState>>, Self::Error> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.accounts_before.get(pubkey).cloned())
+            .collect())
+    }
+
+    async fn simulate(
+        &self,
+        transaction: &VersionedTransaction,
+        pubkeys: &[Pubkey],
+    ) -> Result<SimulationOutput, Self::Error> {
+        let loaded_accounts = transaction.message.static_account_keys();
+        Ok(SimulationOutput {
+            error: self.error.clone(),
+            logs: self.logs.clone(),
+            units_consumed: self.units_consumed,
+            accounts: pubkeys
+                .iter()
+                .map(|pubkey| {
+                    if self.error.is_some() || !loaded_accounts.contains(pubkey) {
+                        return None;
+                    }
+                    self.accounts_after.get(pubkey).cloned()
+                })
+                .collect(),
+        })
+    }
+}
+
+mod rpc {
+    use solana_account_decoder::UiAccountEncoding;
+    use solana_client::{
+        client_error::ClientError,
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    };
+    use solana_sdk::{account::Account, pubkey::Pubkey, transaction::VersionedTransaction};
+
+    use super::{AccountState, SimulationBackend, SimulationOutput};
+
+    impl From<Account> for AccountState {
+        fn from(account: Account) -> Self {
+            Self {
+                lamports: account.lamports,
+                data: account.data,
+            }
+        }
+    }
+
+    impl SimulationBackend for RpcClient {
+        type Error = ClientError;
+
+        async fn get_accounts(
+            &self,
+            pubkeys: &[Pubkey],
+        ) -> Result<Vec<Option<AccountState>>, Self::Error> {
+            Ok(self
+                .get_multiple_accounts(pubkeys)
+                .await?
+                .into_iter()
+                .map(|account| account.map(AccountState::from))
+                .collect())
+        }
+
+        async fn simulate(
+            &self,
+            transaction: &VersionedTransaction,
+            pubkeys: &[Pubkey],
+        ) -> Result<SimulationOutput, Self::Error> {
+            let config = RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: pubkeys.iter().map(ToString::to_string).collect(),
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            };
+            let result = self
+                .simulate_transaction_with_config(transaction, config)
+                .await?
+                .value;
+            Ok(SimulationOutput {
+                error: result.err,
+                logs: result.logs.unwrap_or_default(),
+                units_consumed: result.units_consumed,
+                accounts: result
+                    .accounts
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|account| {
+                        account
+                            .and_then(|account| account.decode::<Account>())
+                            .map(AccountState::from)
+                    })
+                    .collect(),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SimulationError {
+    #[error("Simulation backend failed: {0}")]
+    Backend(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Simulation backend returned {returned} accounts, {expected} were requested")]
+    AccountCountMismatch { expected: usize, returned: usize },
+}
+
+/// Balance of the user in a mint, across its associated token accounts and its lamports for SOL
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BalanceDiff {
+    pub mint: Pubkey,
+    pub pre: u64,
+    /// None when the simulation failed
+    pub post: Option<u64>,
+}
+
+impl BalanceDiff {
+    pub fn change(&self) -> Option<i128> {
+        Some(i128::from(self.post?) - i128::from(self.pre))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReport {
+    pub error: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub input: BalanceDiff,
+    pub output: BalanceDiff,
+    pub swap_mode: SwapMode,
+    /// Quoted out amount
+    pub out_amount: u64,
+    pub other_amount_threshold: u64,
+}
+
+impl SimulationReport {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// None when the simulation failed
+    pub fn input_spent(&self) -> Option<i128> {
+        self.input.change().map(|change| -change)
+    }
+
+    /// None when the simulation failed
+    pub fn output_received(&self) -> Option<i128> {
+        self.output.change()
+    }
+
+    /// Received minus quoted output amount, None when the simulation failed
+    pub fn out_amount_difference(&self) -> Option<i128> {
+        Some(self.output_received()? - i128::from(self.out_amount))
+    }
+
+    /// Whether the simulated amounts honor the slippage, at least the min out amount when ExactIn
+    /// and at most the max in amount when ExactOut. False when the simulation failed
+    pub fn within_threshold(&self) -> bool {
+        let threshold = i128::from(self.other_amount_threshold);
+        match self.swap_mode {
+            SwapMode::ExactIn => self
+                .output_received()
+                .is_some_and(|received| received >= threshold),
+            SwapMode::ExactOut => self.input_spent().is_some_and(|spent| spent <= threshold),
+        }
+    }
+}
+
+/// Accounts holding the balance of `owner` in `mint`, the owner itself is included for SOL
+fn balance_accounts(owner: &Pubkey, mint: &Pubkey) -> Vec<Pubkey> {
+    let mut accounts = [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]
+        .iter()
+        .map(|token_program| associated_token_address(owner, mint, token_program))
+        .collect::<Vec<_>>();
+    if *mint == NATIVE_MINT {
+        accounts.push(*owner);
+    }
+    accounts
+}
+
+fn balance(owner: &Pubkey, pubkeys: &[Pubkey], states: &[Option<AccountState>]) -> u64 {
+    pubkeys
+        .iter()
+        .zip(states)
+        .filter_map(|(pubkey, state)| {
+            let state = state.as_ref()?;
+            if pubkey == owner {
+                Some(state.lamports)
+            } else {
+                state
+                    .data
+                    .get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+                    .and_then(|amount| amount.try_into().ok())
+                    .map(u64::from_le_bytes)
+            }
+        })
+        .fold(0u64, u64::saturating_add)
+}
+
+/// Simulates swap transactions and compares the balance changes of the user with the quote
+#[derive(Clone, Debug)]
+pub struct SwapSimulator<B> {
+    backend: B,
+}
+
+impl<B: SimulationBackend> SwapSimulator<B> {
+    pub fn new(backend: B) -> Self {
+        Self { backend }
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// The SOL balance includes the user lamports, which also pay the transaction and rent fees.
+    ///
+    /// The balances before the swap are read with a separate call to the backend, they may be read at
+    /// a different slot than the one the transaction is simulated at. The accounts not loaded by the
+    /// transaction keep their state from before, accounts loaded through the address lookup tables are
+    /// not resolved so the user accounts are expected to be static accounts, as in Jupiter swaps
+    pub async fn simulate(
+        &self,
+        transaction: &VersionedTransaction,
+        user_public_key: &Pubkey,
+        quote_response: &QuoteResponse,
+    ) -> Result<SimulationReport, SimulationError> {
+        let input_accounts = balance_accounts(user_public_key, &quote_response.input_mint);
+        let output_accounts = balance_accounts(user_public_key, &quote_response.output_mint);
+        let pubkeys = input_accounts
+            .iter()
+            .chain(&output_accounts)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let backend_error = |error: B::Error| SimulationError::Backend(Box::new(error));
+        let before = self
+            .backend
+            .get_accounts(&pubkeys)
+            .await
+            .map_err(backend_error)?;
+        let output = self
+            .backend
+            .simulate(transaction, &pubkeys)
+            .await
+            .map_err(backend_error)?;
+        for returned in [before.len(), output.accounts.len()] {
+            if returned != pubkeys.len() {
+                return Err(SimulationError::AccountCountMismatch {
+                    expected: pubkeys.len(),
+                    returned,
+                });
+            }
+        }
+
+        let after = output.error.is_none().then(|| {
+            let loaded_accounts = transaction.message.static_account_keys();
+            pubkeys
+                .iter()
+                .zip(&before)
+                .zip(output.accounts)
+                .map(|((pubkey, before), after)| match after {
+                    None if !loaded_accounts.contains(pubkey) => before.clone(),
+                    after => after,
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let (input_before, output_before) = before.split_at(input_accounts.len());
+        let post_balance = |accounts: &[Pubkey], range: std::ops::Range<usize>| {
+            after
+                .as_ref()
+                .map(|after| balance(user_public_key, accounts, &after[range]))
+        };
+        Ok(SimulationReport {
+            error: output.error,
+            logs: output.logs,
+            units_consumed: output.units_consumed,
+            input: BalanceDiff {
+                mint: quote_response.input_mint,
+                pre: balance(user_public_key, &input_accounts, input_before),
+                post: post_balance(&input_accounts, 0..input_accounts.len()),
+            },
+            output: BalanceDiff {
+                mint: quote_response.output_mint,
+                pre: balance(user_public_key, &output_accounts, output_before),
+                post: post_balance(&output_accounts, input_accounts.len()..pubkeys.len()),
+            },
+            swap_mode: quote_response.swap_mode.clone(),
+            out_amount: quote_response.out_amount,
+            other_amount_threshold: quote_response.other_amount_threshold,
+        })
+    }
+}
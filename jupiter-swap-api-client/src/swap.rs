@@ -81,6 +81,30 @@ pub mod base64_serialize_deserialize {
     }
 }
 
+/// Same as [`base64_serialize_deserialize`] for optional fields, an empty string is treated as missing
+pub mod option_base64_serialize_deserialize {
+    use serde::{Deserializer, Serializer};
+
+    use super::*;
+    pub fn serialize<S: Serializer>(v: &Option<Vec<u8>>, s: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Some(v) => base64_serialize_deserialize::serialize(v, s),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Base64(#[serde(with = "base64_serialize_deserialize")] Vec<u8>);
+
+        let field = Option::<Base64>::deserialize(deserializer)?;
+        Ok(field.map(|b| b.0).filter(|v| !v.is_empty()))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SwapInstructionsResponse {
     pub token_ledger_instruction: Option<Instruction>,
@@ -100,6 +124,51 @@ pub struct SwapInstructionsResponse {
     pub simulation_error: Option<UiSimulationError>,
 }
 
+/// Role of an instruction in a [`SwapInstructionsResponse`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwapInstructionKind {
+    ComputeBudget,
+    TokenLedger,
+    Setup,
+    Swap,
+    Cleanup,
+    Other,
+}
+
+impl SwapInstructionsResponse {
+    /// All the instructions in the order they should be executed
+    pub fn instructions(&self) -> impl Iterator<Item = (SwapInstructionKind, &Instruction)> {
+        let with_kind = |kind| move |instruction| (kind, instruction);
+        self.compute_budget_instructions
+            .iter()
+            .map(with_kind(SwapInstructionKind::ComputeBudget))
+            .chain(
+                self.token_ledger_instruction
+                    .iter()
+                    .map(with_kind(SwapInstructionKind::TokenLedger)),
+            )
+            .chain(
+                self.setup_instructions
+                    .iter()
+                    .map(with_kind(SwapInstructionKind::Setup)),
+            )
+            .chain(std::iter::once((
+                SwapInstructionKind::Swap,
+                &self.swap_instruction,
+            )))
+            .chain(
+                self.cleanup_instruction
+                    .iter()
+                    .map(with_kind(SwapInstructionKind::Cleanup)),
+            )
+            .chain(
+                self.other_instructions
+                    .iter()
+                    .map(with_kind(SwapInstructionKind::Other)),
+            )
+    }
+}
+
 // Duplicate for deserialization
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
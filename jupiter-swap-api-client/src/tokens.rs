@@ -0,0 +1,187 @@
+//! Token API for mint metadata such as symbol, decimals and tags
+//!
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    deserialize_response,
+    serde_helpers::{field_as_string, option_field_as_string},
+    ClientError, Endpoint, JupiterSwapApiClient,
+};
+
+/// Max number of mints per search query
+pub const MAX_MINTS_PER_REQUEST: usize = 100;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    #[serde(rename = "id", with = "field_as_string")]
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default, with = "option_field_as_string")]
+    pub token_program: Option<Pubkey>,
+    #[serde(default)]
+    pub is_verified: Option<bool>,
+    #[serde(default)]
+    pub tags: Vec<TokenTag>,
+    #[serde(default)]
+    pub organic_score: Option<f64>,
+}
+
+impl TokenInfo {
+    pub fn is_verified(&self) -> bool {
+        self.is_verified.unwrap_or(false) || self.tags.contains(&TokenTag::Verified)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum TokenTag {
+    Verified,
+    Strict,
+    Community,
+    Lst,
+    Other(String),
+}
+
+impl TokenTag {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Verified => "verified",
+            Self::Strict => "strict",
+            Self::Community => "community",
+            Self::Lst => "lst",
+            Self::Other(tag) => tag,
+        }
+    }
+}
+
+impl From<String> for TokenTag {
+    fn from(tag: String) -> Self {
+        match tag.as_str() {
+            "verified" => Self::Verified,
+            "strict" => Self::Strict,
+            "community" => Self::Community,
+            "lst" => Self::Lst,
+            _ => Self::Other(tag),
+        }
+    }
+}
+
+impl From<TokenTag> for String {
+    fn from(tag: TokenTag) -> Self {
+        match tag {
+            TokenTag::Other(tag) => tag,
+            tag => tag.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TokenTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// In-memory cache of token infos keyed by mint, clones share the same cache
+#[derive(Clone, Debug, Default)]
+pub struct TokenCache(Arc<RwLock<HashMap<Pubkey, TokenInfo>>>);
+
+impl TokenCache {
+    pub fn get(&self, mint: &Pubkey) -> Option<TokenInfo> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(mint)
+            .cloned()
+    }
+
+    pub fn insert(&self, token_infos: impl IntoIterator<Item = TokenInfo>) {
+        let mut cache = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        for token_info in token_infos {
+            cache.insert(token_info.mint, token_info);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl JupiterSwapApiClient {
+    /// Search tokens by symbol, name or comma separated mints
+    pub async fn search_tokens(&self, query: &str) -> Result<Vec<TokenInfo>, ClientError> {
+        let request = self
+            .request(Method::GET, &format!("{}/search", self.tokens_base_path))
+            .query(&[("query", query)]);
+        let response = self.send(Endpoint::Tokens, request).await?;
+        let token_infos: Vec<TokenInfo> = deserialize_response(response).await?;
+        self.token_cache.insert(token_infos.iter().cloned());
+        Ok(token_infos)
+    }
+
+    pub async fn tokens_by_tag(&self, tag: &TokenTag) -> Result<Vec<TokenInfo>, ClientError> {
+        let request = self
+            .request(Method::GET, &format!("{}/tag", self.tokens_base_path))
+            .query(&[("query", tag.as_str())]);
+        let response = self.send(Endpoint::Tokens, request).await?;
+        let token_infos: Vec<TokenInfo> = deserialize_response(response).await?;
+        self.token_cache.insert(token_infos.iter().cloned());
+        Ok(token_infos)
+    }
+
+    /// Token info of the mint, served from the token cache when available
+    pub async fn token_info(&self, mint: &Pubkey) -> Result<Option<TokenInfo>, ClientError> {
+        let mut token_infos = self.token_infos(&[*mint]).await?;
+        Ok(token_infos.remove(mint))
+    }
+
+    /// Token infos of the mints, served from the token cache when available.
+    /// Mints unknown to the API are missing from the result.
+    pub async fn token_infos(
+        &self,
+        mints: &[Pubkey],
+    ) -> Result<HashMap<Pubkey, TokenInfo>, ClientError> {
+        let mut token_infos = HashMap::with_capacity(mints.len());
+        let mut missing_mints = Vec::new();
+        for mint in mints {
+            match self.token_cache.get(mint) {
+                Some(token_info) => {
+                    token_infos.insert(*mint, token_info);
+                }
+                None => missing_mints.push(*mint),
+            }
+        }
+        missing_mints.sort();
+        missing_mints.dedup();
+
+        for chunk in missing_mints.chunks(MAX_MINTS_PER_REQUEST) {
+            let query = chunk
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            for token_info in self.search_tokens(&query).await? {
+                if chunk.contains(&token_info.mint) {
+                    token_infos.insert(token_info.mint, token_info);
+                }
+            }
+        }
+        Ok(token_infos)
+    }
+}
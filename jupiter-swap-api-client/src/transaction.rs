@@ -0,0 +1,157 @@
+//! Decoding and signing of the transactions returned by the APIs
+//!
+
+use solana_sdk::{
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{Signer, SignerError},
+    transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+use crate::swap::{SwapRequest, SwapResponse};
+
+#[derive(Debug, Error)]
+pub enum TransactionError {
+    #[error("Failed to encode or decode transaction: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("Expected a legacy transaction, got a versioned one")]
+    ExpectedLegacy,
+    #[error("Expected a versioned transaction, got a legacy one")]
+    ExpectedVersioned,
+    #[error("Signer {0} is not a required signer of the transaction")]
+    UnexpectedSigner(Pubkey),
+    #[error("Missing signatures of {0:?}")]
+    MissingSigners(Vec<Pubkey>),
+    #[error("Fee payer {fee_payer} is not the user {user}")]
+    FeePayerMismatch { fee_payer: Pubkey, user: Pubkey },
+    #[error("None of the signers {signers:?} is the user {user}")]
+    SignerMismatch { user: Pubkey, signers: Vec<Pubkey> },
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+}
+
+pub fn decode_transaction(transaction: &[u8]) -> Result<VersionedTransaction, TransactionError> {
+    Ok(bincode::deserialize(transaction)?)
+}
+
+pub fn encode_transaction(transaction: &VersionedTransaction) -> Result<Vec<u8>, TransactionError> {
+    Ok(bincode::serialize(transaction)?)
+}
+
+/// Add the signatures of `signers` to the transaction, signatures of other signers are kept
+pub fn partial_sign(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+) -> Result<(), TransactionError> {
+    sign_positions(transaction, signers).map(|_| ())
+}
+
+/// Sign the transaction, failing if any required signature is neither provided by `signers` nor already present
+pub fn sign(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+) -> Result<(), TransactionError> {
+    let signed_positions = sign_positions(transaction, signers)?;
+    let missing_signers = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .zip(&transaction.signatures)
+        .enumerate()
+        .filter(|(position, (_, signature))| {
+            !signed_positions.contains(position) && **signature == Signature::default()
+        })
+        .map(|(_, (key, _))| *key)
+        .collect::<Vec<_>>();
+    if missing_signers.is_empty() {
+        Ok(())
+    } else {
+        Err(TransactionError::MissingSigners(missing_signers))
+    }
+}
+
+/// Positions of the signatures added by `signers`
+fn sign_positions(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+) -> Result<Vec<usize>, TransactionError> {
+    let num_required_signatures = usize::from(transaction.message.header().num_required_signatures);
+    let required_signers = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .take(num_required_signatures)
+        .copied()
+        .collect::<Vec<_>>();
+    transaction
+        .signatures
+        .resize(num_required_signatures, Signature::default());
+
+    let message = transaction.message.serialize();
+    let mut positions = Vec::with_capacity(signers.len());
+    for signer in signers {
+        let signer_pubkey = signer.try_pubkey()?;
+        let position = required_signers
+            .iter()
+            .position(|key| *key == signer_pubkey)
+            .ok_or(TransactionError::UnexpectedSigner(signer_pubkey))?;
+        transaction.signatures[position] = signer.try_sign_message(&message)?;
+        positions.push(position);
+    }
+    Ok(positions)
+}
+
+impl SwapResponse {
+    pub fn versioned_transaction(&self) -> Result<VersionedTransaction, TransactionError> {
+        decode_transaction(&self.swap_transaction)
+    }
+
+    /// Sign the swap transaction, all the required signers have to be provided.
+    /// The transaction is not checked against the swap request, the fee payer, user signer and
+    /// legacy checks are only run by [`SwapResponse::sign_for_request`].
+    pub fn sign(&self, signers: &[&dyn Signer]) -> Result<VersionedTransaction, TransactionError> {
+        let mut transaction = self.versioned_transaction()?;
+        sign(&mut transaction, signers)?;
+        Ok(transaction)
+    }
+
+    /// Sign the swap transaction after checking that it matches the request it was built from
+    pub fn sign_for_request(
+        &self,
+        swap_request: &SwapRequest,
+        signers: &[&dyn Signer],
+    ) -> Result<VersionedTransaction, TransactionError> {
+        let mut transaction = self.versioned_transaction()?;
+        let is_legacy = matches!(transaction.message, VersionedMessage::Legacy(_));
+        match (swap_request.config.as_legacy_transaction, is_legacy) {
+            (true, false) => return Err(TransactionError::ExpectedLegacy),
+            (false, true) => return Err(TransactionError::ExpectedVersioned),
+            _ => {}
+        }
+
+        let user = swap_request.user_public_key;
+        if let Some(fee_payer) = transaction.message.static_account_keys().first() {
+            if *fee_payer != user {
+                return Err(TransactionError::FeePayerMismatch {
+                    fee_payer: *fee_payer,
+                    user,
+                });
+            }
+        }
+        let signer_pubkeys = signers
+            .iter()
+            .map(|signer| signer.try_pubkey())
+            .collect::<Result<Vec<_>, _>>()?;
+        if !signer_pubkeys.contains(&user) {
+            return Err(TransactionError::SignerMismatch {
+                user,
+                signers: signer_pubkeys,
+            });
+        }
+
+        sign(&mut transaction, signers)?;
+        Ok(transaction)
+    }
+}
@@ -0,0 +1,248 @@
+//! Trigger API to place, cancel and list limit orders
+//!
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize, Serializer};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    deserialize_response,
+    serde_helpers::{field_as_string, option_field_as_string},
+    swap::base64_serialize_deserialize,
+    ClientError, Endpoint, JupiterSwapApiClient,
+};
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTriggerOrderRequest {
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub maker: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub payer: Pubkey,
+    pub params: TriggerOrderParams,
+    /// Defaults to auto
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price: Option<u64>,
+    /// Referral token account receiving the fee, required when `fee_bps` is set
+    #[serde(
+        with = "option_field_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_account: Option<Pubkey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap_and_unwrap_sol: Option<bool>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerOrderParams {
+    /// Amount of input mint to sell, have to factor in the token decimals.
+    #[serde(with = "field_as_string")]
+    pub making_amount: u64,
+    /// Amount of output mint to receive, have to factor in the token decimals.
+    #[serde(with = "field_as_string")]
+    pub taking_amount: u64,
+    /// Unix timestamp in seconds after which the order can no longer be filled
+    #[serde(
+        with = "option_field_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expired_at: Option<i64>,
+    #[serde(
+        with = "option_field_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub slippage_bps: Option<u16>,
+    #[serde(
+        with = "option_field_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_bps: Option<u16>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTriggerOrderResponse {
+    /// Account of the created order
+    #[serde(with = "field_as_string")]
+    pub order: Pubkey,
+    /// Unsigned transaction creating the order
+    #[serde(with = "base64_serialize_deserialize")]
+    pub transaction: Vec<u8>,
+    pub request_id: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTriggerOrderRequest {
+    #[serde(with = "field_as_string")]
+    pub maker: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub order: Pubkey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTriggerOrderResponse {
+    #[serde(with = "base64_serialize_deserialize")]
+    pub transaction: Vec<u8>,
+    pub request_id: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTriggerOrdersRequest {
+    #[serde(with = "field_as_string")]
+    pub maker: Pubkey,
+    /// Orders to cancel, all the open orders of the maker if empty
+    #[serde(
+        serialize_with = "serialize_pubkeys",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub orders: Vec<Pubkey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTriggerOrdersResponse {
+    /// Unsigned transactions, the orders are batched over several transactions
+    pub transactions: Vec<TriggerTransaction>,
+    pub request_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TriggerTransaction(#[serde(with = "base64_serialize_deserialize")] pub Vec<u8>);
+
+/// Status of the orders to list
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderStatusFilter {
+    Active,
+    History,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerOrdersRequest {
+    #[serde(with = "field_as_string")]
+    pub user: Pubkey,
+    pub order_status: OrderStatusFilter,
+    /// Starts at 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(
+        with = "option_field_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub input_mint: Option<Pubkey>,
+    #[serde(
+        with = "option_field_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub output_mint: Option<Pubkey>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerOrdersResponse {
+    pub orders: Vec<TriggerOrder>,
+    #[serde(default)]
+    pub total_pages: u32,
+    #[serde(default)]
+    pub page: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerOrder {
+    #[serde(with = "field_as_string")]
+    pub user_pubkey: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub order_key: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub raw_making_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub raw_taking_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub raw_remaining_making_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub raw_remaining_taking_amount: u64,
+    #[serde(default, with = "option_field_as_string")]
+    pub slippage_bps: Option<u16>,
+    #[serde(default)]
+    pub expired_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub status: TriggerOrderStatus,
+    #[serde(default)]
+    pub open_tx: Option<String>,
+    #[serde(default)]
+    pub close_tx: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerOrderStatus {
+    Open,
+    Completed,
+    Cancelled,
+    #[serde(other)]
+    Unknown,
+}
+
+fn serialize_pubkeys<S: Serializer>(pubkeys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(pubkeys.iter().map(ToString::to_string))
+}
+
+impl JupiterSwapApiClient {
+    pub async fn create_trigger_order(
+        &self,
+        create_request: &CreateTriggerOrderRequest,
+    ) -> Result<CreateTriggerOrderResponse, ClientError> {
+        let url = format!("{}/createOrder", self.trigger_base_path);
+        let request = self.request(Method::POST, &url).json(create_request);
+        let response = self.send(Endpoint::TriggerCreateOrder, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn cancel_trigger_order(
+        &self,
+        cancel_request: &CancelTriggerOrderRequest,
+    ) -> Result<CancelTriggerOrderResponse, ClientError> {
+        let url = format!("{}/cancelOrder", self.trigger_base_path);
+        let request = self.request(Method::POST, &url).json(cancel_request);
+        let response = self.send(Endpoint::TriggerCancelOrder, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn cancel_trigger_orders(
+        &self,
+        cancel_request: &CancelTriggerOrdersRequest,
+    ) -> Result<CancelTriggerOrdersResponse, ClientError> {
+        let url = format!("{}/cancelOrders", self.trigger_base_path);
+        let request = self.request(Method::POST, &url).json(cancel_request);
+        let response = self.send(Endpoint::TriggerCancelOrder, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn trigger_orders(
+        &self,
+        orders_request: &TriggerOrdersRequest,
+    ) -> Result<TriggerOrdersResponse, ClientError> {
+        let url = format!("{}/getTriggerOrders", self.trigger_base_path);
+        let request = self.request(Method::GET, &url).query(orders_request);
+        let response = self.send(Endpoint::TriggerOrders, request).await?;
+        deserialize_response(response).await
+    }
+}
@@ -0,0 +1,207 @@
+//! Ultra API, the order is quoted and built by the server then landed by the server on execute
+//!
+
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Signature, signer::Signer, transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+use crate::{
+    deserialize_response,
+    dexes::DexSet,
+    quote::SwapMode,
+    route_plan_with_metadata::RoutePlanWithMetadata,
+    serde_helpers::{field_as_string, option_field_as_string},
+    swap::{base64_serialize_deserialize, option_base64_serialize_deserialize},
+    transaction::{decode_transaction, encode_transaction, partial_sign, TransactionError},
+    ClientError, Endpoint, JupiterSwapApiClient,
+};
+
+#[derive(Debug, Error)]
+pub enum UltraError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error("The order has no transaction, the taker was not provided")]
+    MissingTransaction,
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UltraOrderRequest {
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    /// The amount to swap, have to factor in the token decimals.
+    #[serde(with = "field_as_string")]
+    pub amount: u64,
+    /// The user signing the transaction, no transaction is returned without it
+    #[serde(with = "option_field_as_string")]
+    pub taker: Option<Pubkey>,
+    #[serde(with = "option_field_as_string")]
+    pub referral_account: Option<Pubkey>,
+    /// Referral fee in basis points
+    pub referral_fee: Option<u16>,
+    pub exclude_dexes: Option<DexSet>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UltraOrderResponse {
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub in_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub out_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub other_amount_threshold: u64,
+    pub swap_mode: SwapMode,
+    pub slippage_bps: u16,
+    #[serde(with = "field_as_string")]
+    pub price_impact_pct: Decimal,
+    pub route_plan: RoutePlanWithMetadata,
+    #[serde(default, with = "option_field_as_string")]
+    pub fee_mint: Option<Pubkey>,
+    #[serde(default)]
+    pub fee_bps: Option<u16>,
+    #[serde(default, with = "option_field_as_string")]
+    pub taker: Option<Pubkey>,
+    #[serde(default)]
+    pub gasless: bool,
+    /// Unsigned transaction, missing when the taker was not provided
+    #[serde(default, with = "option_base64_serialize_deserialize")]
+    pub transaction: Option<Vec<u8>>,
+    #[serde(default)]
+    pub prioritization_fee_lamports: Option<u64>,
+    pub request_id: String,
+    #[serde(default)]
+    pub swap_type: Option<String>,
+    #[serde(default)]
+    pub router: Option<String>,
+}
+
+impl UltraOrderResponse {
+    pub fn versioned_transaction(&self) -> Result<VersionedTransaction, UltraError> {
+        let transaction = self
+            .transaction
+            .as_ref()
+            .ok_or(UltraError::MissingTransaction)?;
+        Ok(decode_transaction(transaction)?)
+    }
+
+    /// Sign the order transaction, other signatures such as the one of a gasless payer are kept
+    pub fn sign(&self, signer: &dyn Signer) -> Result<VersionedTransaction, UltraError> {
+        let mut transaction = self.versioned_transaction()?;
+        partial_sign(&mut transaction, &[signer])?;
+        Ok(transaction)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UltraExecuteRequest {
+    #[serde(with = "base64_serialize_deserialize")]
+    pub signed_transaction: Vec<u8>,
+    pub request_id: String,
+}
+
+impl UltraExecuteRequest {
+    pub fn new(
+        signed_transaction: &VersionedTransaction,
+        request_id: String,
+    ) -> Result<Self, UltraError> {
+        Ok(Self {
+            signed_transaction: encode_transaction(signed_transaction)?,
+            request_id,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UltraExecuteStatus {
+    Success,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UltraExecuteResponse {
+    pub status: UltraExecuteStatus,
+    #[serde(default, with = "option_field_as_string")]
+    pub signature: Option<Signature>,
+    #[serde(default, with = "option_field_as_string")]
+    pub slot: Option<u64>,
+    #[serde(default)]
+    pub code: i32,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default, with = "option_field_as_string")]
+    pub total_input_amount: Option<u64>,
+    #[serde(default, with = "option_field_as_string")]
+    pub total_output_amount: Option<u64>,
+    #[serde(default, with = "option_field_as_string")]
+    pub input_amount_result: Option<u64>,
+    #[serde(default, with = "option_field_as_string")]
+    pub output_amount_result: Option<u64>,
+    #[serde(default)]
+    pub swap_events: Vec<UltraSwapEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UltraSwapEvent {
+    #[serde(with = "field_as_string")]
+    pub input_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub input_amount: u64,
+    #[serde(with = "field_as_string")]
+    pub output_mint: Pubkey,
+    #[serde(with = "field_as_string")]
+    pub output_amount: u64,
+}
+
+impl JupiterSwapApiClient {
+    pub async fn ultra_order(
+        &self,
+        order_request: &UltraOrderRequest,
+    ) -> Result<UltraOrderResponse, ClientError> {
+        let request = self
+            .request(Method::GET, &format!("{}/order", self.ultra_base_path))
+            .query(order_request);
+        let response = self.send(Endpoint::UltraOrder, request).await?;
+        deserialize_response(response).await
+    }
+
+    pub async fn ultra_execute(
+        &self,
+        execute_request: &UltraExecuteRequest,
+    ) -> Result<UltraExecuteResponse, ClientError> {
+        let request = self
+            .request(Method::POST, &format!("{}/execute", self.ultra_base_path))
+            .json(execute_request);
+        let response = self.send(Endpoint::UltraExecute, request).await?;
+        deserialize_response(response).await
+    }
+
+    /// Sign the order transaction with the taker and execute it
+    pub async fn ultra_sign_and_execute(
+        &self,
+        order_response: &UltraOrderResponse,
+        signer: &dyn Signer,
+    ) -> Result<UltraExecuteResponse, UltraError> {
+        let signed_transaction = order_response.sign(signer)?;
+        let execute_request =
+            UltraExecuteRequest::new(&signed_transaction, order_response.request_id.clone())?;
+        Ok(self.ultra_execute(&execute_request).await?)
+    }
+}
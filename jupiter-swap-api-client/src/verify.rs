@@ -0,0 +1,696 @@
+//! Safety checks of the swap transaction before signing it, the transaction built by the API is not trusted
+//!
+
+use std::collections::{HashMap, HashSet};
+
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount, compute_budget, instruction::Instruction,
+    message::VersionedMessage, pubkey, pubkey::Pubkey, system_instruction::SystemInstruction,
+    system_program, transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+use crate::{
+    quote::QuoteResponse,
+    swap::{SwapInstructionsResponse, SwapResponse},
+    transaction::TransactionError,
+};
+
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWK25efTNsLJA8knL");
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// SPL token instruction tags, shared by token 2022
+const TOKEN_INITIALIZE_ACCOUNT: u8 = 1;
+const TOKEN_TRANSFER: u8 = 3;
+const TOKEN_APPROVE: u8 = 4;
+const TOKEN_REVOKE: u8 = 5;
+const TOKEN_SET_AUTHORITY: u8 = 6;
+const TOKEN_CLOSE_ACCOUNT: u8 = 9;
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+const TOKEN_APPROVE_CHECKED: u8 = 13;
+const TOKEN_INITIALIZE_ACCOUNT_2: u8 = 16;
+const TOKEN_SYNC_NATIVE: u8 = 17;
+const TOKEN_INITIALIZE_ACCOUNT_3: u8 = 18;
+const TOKEN_INITIALIZE_IMMUTABLE_OWNER: u8 = 22;
+// Token 2022 transfer fee extension and its transfer checked with fee instruction
+const TOKEN_2022_TRANSFER_FEE_EXTENSION: u8 = 26;
+const TOKEN_2022_TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+
+const COMPUTE_BUDGET_SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+// Associated token program instruction tags, an empty data is a create
+const ASSOCIATED_TOKEN_CREATE: u8 = 0;
+const ASSOCIATED_TOKEN_CREATE_IDEMPOTENT: u8 = 1;
+
+// Jupiter instruction discriminators, the first 8 bytes of sha256("global:<instruction name>")
+const JUPITER_ROUTE: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+const JUPITER_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [150, 86, 71, 116, 167, 93, 14, 104];
+const JUPITER_EXACT_OUT_ROUTE: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+const JUPITER_SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+const JUPITER_SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] =
+    [230, 121, 143, 80, 119, 159, 106, 170];
+const JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE: [u8; 8] = [176, 209, 105, 168, 154, 125, 69, 62];
+const JUPITER_CREATE_TOKEN_LEDGER: [u8; 8] = [232, 242, 197, 253, 240, 143, 129, 52];
+const JUPITER_SET_TOKEN_LEDGER: [u8; 8] = [228, 85, 185, 112, 78, 79, 77, 2];
+
+/// Programs allowed to own the accounts created by the system instructions of the swap
+const NEW_ACCOUNT_OWNERS: [Pubkey; 3] =
+    [system_program::ID, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID];
+
+/// Rule broken by an instruction of the swap, `index` is the position of the instruction
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Violation {
+    #[error("Instruction {index} invokes program {program_id} which is not allowed")]
+    ProgramNotAllowed { index: usize, program_id: Pubkey },
+    #[error("Fee payer {fee_payer} is not the user")]
+    FeePayerMismatch { fee_payer: Pubkey },
+    #[error("{signer} is required to sign besides the user")]
+    UnexpectedSigner { signer: Pubkey },
+    #[error("Instruction {index} transfers {lamports} lamports to {destination}")]
+    UnexpectedSolTransfer {
+        index: usize,
+        destination: Pubkey,
+        lamports: u64,
+    },
+    #[error("Instruction {index} transfers tokens to {destination}")]
+    UnexpectedTokenTransfer { index: usize, destination: Pubkey },
+    #[error("Instruction {index} approves {delegate} as delegate")]
+    UnexpectedApproval { index: usize, delegate: Pubkey },
+    #[error("Instruction {index} sets the authority of {account} to {new_authority:?}")]
+    ForeignAuthority {
+        index: usize,
+        account: Pubkey,
+        new_authority: Option<Pubkey>,
+    },
+    #[error("Instruction {index} assigns {account} to program {owner}")]
+    ForeignAccountOwner {
+        index: usize,
+        account: Pubkey,
+        owner: Pubkey,
+    },
+    #[error("Instruction {index} closes {account} to {destination}")]
+    ForeignCloseDestination {
+        index: usize,
+        account: Pubkey,
+        destination: Pubkey,
+    },
+    #[error("Instruction {index} sets a compute unit price of {price} above {max_price}")]
+    ComputeUnitPriceTooHigh {
+        index: usize,
+        price: u64,
+        max_price: u64,
+    },
+    #[error("Instruction {index} creates a token account of mint {mint} which is not quoted")]
+    UnexpectedMint { index: usize, mint: Pubkey },
+    #[error("Quoted mint {0} is not referenced by the transaction")]
+    MintNotReferenced(Pubkey),
+    #[error("Address lookup table {0} was not provided, its accounts are not checked")]
+    UnresolvedLookupTable(Pubkey),
+    #[error("Instruction {index} could not be decoded")]
+    UndecodableInstruction { index: usize },
+    #[error("Instruction {index} of program {program_id} is not an allowed instruction")]
+    InstructionNotAllowed { index: usize, program_id: Pubkey },
+    #[error("Swap instruction {index} sends the output to {destination}")]
+    UnexpectedSwapDestination { index: usize, destination: Pubkey },
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl VerificationReport {
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Instruction with its accounts resolved, None for accounts of lookup tables that were not provided
+/// or do not hold the account
+struct ResolvedInstruction {
+    program_id: Pubkey,
+    accounts: Vec<Option<Pubkey>>,
+    data: Vec<u8>,
+}
+
+impl ResolvedInstruction {
+    fn account(&self, position: usize) -> Option<Pubkey> {
+        self.accounts.get(position).copied().flatten()
+    }
+}
+
+/// Checks the swap transaction against the user and the quote it was built from
+#[derive(Clone, Debug)]
+pub struct SwapVerifier {
+    user: Pubkey,
+    allowed_programs: HashSet<Pubkey>,
+    allowed_destinations: HashSet<Pubkey>,
+    destination_token_account: Option<Pubkey>,
+    max_compute_unit_price: Option<u64>,
+}
+
+impl SwapVerifier {
+    /// Allows the Jupiter, system, token, token 2022, associated token and compute budget programs
+    pub fn new(user: Pubkey) -> Self {
+        Self {
+            user,
+            allowed_programs: HashSet::from([
+                JUPITER_PROGRAM_ID,
+                system_program::id(),
+                TOKEN_PROGRAM_ID,
+                TOKEN_2022_PROGRAM_ID,
+                ASSOCIATED_TOKEN_PROGRAM_ID,
+                compute_budget::id(),
+            ]),
+            allowed_destinations: HashSet::new(),
+            destination_token_account: None,
+            max_compute_unit_price: None,
+        }
+    }
+
+    pub fn allow_program(mut self, program_id: Pubkey) -> Self {
+        self.allowed_programs.insert(program_id);
+        self
+    }
+
+    /// Account allowed to receive SOL or tokens or to be a delegate, such as a Jito tip account
+    pub fn allow_destination(mut self, destination: Pubkey) -> Self {
+        self.allowed_destinations.insert(destination);
+        self
+    }
+
+    /// Token account receiving the output of the swap instead of the user associated token account,
+    /// the `destination_token_account` of the swap request
+    pub fn destination_token_account(mut self, destination_token_account: Pubkey) -> Self {
+        self.destination_token_account = Some(destination_token_account);
+        self
+    }
+
+    /// Max compute unit price in micro lamports
+    pub fn max_compute_unit_price(mut self, max_compute_unit_price: u64) -> Self {
+        self.max_compute_unit_price = Some(max_compute_unit_price);
+        self
+    }
+
+    pub fn verify_swap_response(
+        &self,
+        swap_response: &SwapResponse,
+        quote_response: &QuoteResponse,
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VerificationReport, TransactionError> {
+        let transaction = swap_response.versioned_transaction()?;
+        Ok(self.verify_transaction(&transaction, quote_response, address_lookup_tables))
+    }
+
+    /// `address_lookup_tables` resolve the accounts loaded by a v0 transaction,
+    /// accounts of missing tables are reported and left unchecked
+    pub fn verify_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        quote_response: &QuoteResponse,
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> VerificationReport {
+        let mut violations = Vec::new();
+        let message = &transaction.message;
+        let static_keys = message.static_account_keys();
+
+        match static_keys.first() {
+            Some(fee_payer) if *fee_payer != self.user => {
+                violations.push(Violation::FeePayerMismatch {
+                    fee_payer: *fee_payer,
+                })
+            }
+            _ => {}
+        }
+        let num_required_signatures = usize::from(message.header().num_required_signatures);
+        violations.extend(
+            static_keys
+                .iter()
+                .take(num_required_signatures)
+                .filter(|key| **key != self.user)
+                .map(|signer| Violation::UnexpectedSigner { signer: *signer }),
+        );
+
+        let mut account_keys = static_keys.iter().copied().map(Some).collect::<Vec<_>>();
+        if let VersionedMessage::V0(message) = message {
+            let tables = address_lookup_tables
+                .iter()
+                .map(|table| (table.key, &table.addresses))
+                .collect::<HashMap<_, _>>();
+            let mut readonly = Vec::new();
+            for lookup in &message.address_table_lookups {
+                let table = tables.get(&lookup.account_key);
+                let resolve = |index: &u8| {
+                    table.and_then(|addresses| addresses.get(usize::from(*index)).copied())
+                };
+                let writable = lookup
+                    .writable_indexes
+                    .iter()
+                    .map(resolve)
+                    .collect::<Vec<_>>();
+                let readonly_accounts = lookup
+                    .readonly_indexes
+                    .iter()
+                    .map(resolve)
+                    .collect::<Vec<_>>();
+                // Missing table or a stale one, shorter than the indexes of the message
+                if writable
+                    .iter()
+                    .chain(&readonly_accounts)
+                    .any(Option::is_none)
+                {
+                    violations.push(Violation::UnresolvedLookupTable(lookup.account_key));
+                }
+                account_keys.extend(writable);
+                readonly.extend(readonly_accounts);
+            }
+            account_keys.extend(readonly);
+        }
+
+        // Programs cannot be loaded from lookup tables, out of range indexes make the instruction undecodable
+        let instructions = message
+            .instructions()
+            .iter()
+            .map(|instruction| {
+                Some(ResolvedInstruction {
+                    program_id: *static_keys.get(usize::from(instruction.program_id_index))?,
+                    accounts: instruction
+                        .accounts
+                        .iter()
+                        .map(|index| account_keys.get(usize::from(*index)).copied())
+                        .collect::<Option<_>>()?,
+                    data: instruction.data.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        self.verify_instructions(
+            &instructions,
+            quote_response,
+            &account_keys,
+            &mut violations,
+        );
+        VerificationReport { violations }
+    }
+
+    /// The payer is chosen when assembling the transaction, only the signers of the instructions are checked
+    pub fn verify_swap_instructions(
+        &self,
+        swap_instructions: &SwapInstructionsResponse,
+        quote_response: &QuoteResponse,
+    ) -> VerificationReport {
+        let mut violations = Vec::new();
+        let instructions = swap_instructions
+            .instructions()
+            .map(|(_, instruction)| instruction)
+            .collect::<Vec<&Instruction>>();
+
+        let mut signers = Vec::new();
+        for account in instructions
+            .iter()
+            .flat_map(|instruction| &instruction.accounts)
+        {
+            if account.is_signer
+                && account.pubkey != self.user
+                && !signers.contains(&account.pubkey)
+            {
+                signers.push(account.pubkey);
+            }
+        }
+        violations.extend(
+            signers
+                .into_iter()
+                .map(|signer| Violation::UnexpectedSigner { signer }),
+        );
+
+        let account_keys = instructions
+            .iter()
+            .flat_map(|instruction| {
+                std::iter::once(instruction.program_id)
+                    .chain(instruction.accounts.iter().map(|account| account.pubkey))
+            })
+            .map(Some)
+            .collect::<Vec<_>>();
+        let instructions = instructions
+            .into_iter()
+            .map(|instruction| {
+                Some(ResolvedInstruction {
+                    program_id: instruction.program_id,
+                    accounts: instruction
+                        .accounts
+                        .iter()
+                        .map(|account| Some(account.pubkey))
+                        .collect(),
+                    data: instruction.data.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        self.verify_instructions(
+            &instructions,
+            quote_response,
+            &account_keys,
+            &mut violations,
+        );
+        VerificationReport { violations }
+    }
+
+    fn verify_instructions(
+        &self,
+        instructions: &[Option<ResolvedInstruction>],
+        quote_response: &QuoteResponse,
+        account_keys: &[Option<Pubkey>],
+        violations: &mut Vec<Violation>,
+    ) {
+        let quoted_mints = [quote_response.input_mint, quote_response.output_mint];
+        let user_token_accounts = quoted_mints
+            .iter()
+            .chain([&NATIVE_MINT])
+            .flat_map(|mint| {
+                [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]
+                    .map(|token_program| associated_token_address(&self.user, mint, &token_program))
+            })
+            .collect::<HashSet<_>>();
+        let is_user_destination = |destination: &Pubkey| {
+            *destination == self.user
+                || user_token_accounts.contains(destination)
+                || self.allowed_destinations.contains(destination)
+        };
+
+        for (index, instruction) in instructions.iter().enumerate() {
+            let Some(instruction) = instruction else {
+                violations.push(Violation::UndecodableInstruction { index });
+                continue;
+            };
+            if !self.allowed_programs.contains(&instruction.program_id) {
+                violations.push(Violation::ProgramNotAllowed {
+                    index,
+                    program_id: instruction.program_id,
+                });
+                continue;
+            }
+
+            if instruction.program_id == system_program::id() {
+                let Ok(system_instruction) =
+                    bincode::deserialize::<SystemInstruction>(&instruction.data)
+                else {
+                    violations.push(Violation::UndecodableInstruction { index });
+                    continue;
+                };
+                let (destination, lamports) = match system_instruction {
+                    SystemInstruction::Transfer { lamports } => (instruction.account(1), lamports),
+                    SystemInstruction::TransferWithSeed { lamports, .. } => {
+                        (instruction.account(2), lamports)
+                    }
+                    // Funding account then the created account, its lamports go to the owner program
+                    SystemInstruction::CreateAccount { owner, .. }
+                    | SystemInstruction::CreateAccountWithSeed { owner, .. } => {
+                        if !NEW_ACCOUNT_OWNERS.contains(&owner) {
+                            violations.push(Violation::ForeignAccountOwner {
+                                index,
+                                account: instruction.account(1).unwrap_or_default(),
+                                owner,
+                            });
+                        }
+                        continue;
+                    }
+                    SystemInstruction::Assign { owner }
+                    | SystemInstruction::AssignWithSeed { owner, .. } => {
+                        if owner != system_program::id() {
+                            violations.push(Violation::ForeignAccountOwner {
+                                index,
+                                account: instruction.account(0).unwrap_or_default(),
+                                owner,
+                            });
+                        }
+                        continue;
+                    }
+                    SystemInstruction::AdvanceNonceAccount => continue,
+                    _ => {
+                        violations.push(Violation::InstructionNotAllowed {
+                            index,
+                            program_id: instruction.program_id,
+                        });
+                        continue;
+                    }
+                };
+                if let Some(destination) = destination {
+                    if !is_user_destination(&destination) {
+                        violations.push(Violation::UnexpectedSolTransfer {
+                            index,
+                            destination,
+                            lamports,
+                        });
+                    }
+                }
+            } else if instruction.program_id == TOKEN_PROGRAM_ID
+                || instruction.program_id == TOKEN_2022_PROGRAM_ID
+            {
+                self.verify_token_instruction(index, instruction, &is_user_destination, violations);
+            } else if instruction.program_id == compute_budget::id() {
+                let Some(max_price) = self.max_compute_unit_price else {
+                    continue;
+                };
+                if instruction.data.first() != Some(&COMPUTE_BUDGET_SET_COMPUTE_UNIT_PRICE) {
+                    continue;
+                }
+                let Some(price) = instruction
+                    .data
+                    .get(1..9)
+                    .and_then(|price| price.try_into().ok())
+                    .map(u64::from_le_bytes)
+                else {
+                    violations.push(Violation::UndecodableInstruction { index });
+                    continue;
+                };
+                if price > max_price {
+                    violations.push(Violation::ComputeUnitPriceTooHigh {
+                        index,
+                        price,
+                        max_price,
+                    });
+                }
+            } else if instruction.program_id == ASSOCIATED_TOKEN_PROGRAM_ID {
+                match instruction.data.first() {
+                    // Create and create idempotent: payer, associated account, owner, mint
+                    None
+                    | Some(&ASSOCIATED_TOKEN_CREATE)
+                    | Some(&ASSOCIATED_TOKEN_CREATE_IDEMPOTENT) => {
+                        if let (Some(owner), Some(mint)) =
+                            (instruction.account(2), instruction.account(3))
+                        {
+                            if owner == self.user && !quoted_mints.contains(&mint) {
+                                violations.push(Violation::UnexpectedMint { index, mint });
+                            }
+                        }
+                    }
+                    // Recover nested moves tokens out of a nested account, not part of a swap
+                    _ => violations.push(Violation::InstructionNotAllowed {
+                        index,
+                        program_id: instruction.program_id,
+                    }),
+                }
+            } else if instruction.program_id == JUPITER_PROGRAM_ID {
+                self.verify_jupiter_instruction(
+                    index,
+                    instruction,
+                    &quote_response.output_mint,
+                    violations,
+                );
+            }
+        }
+
+        if account_keys.iter().all(Option::is_some) {
+            for mint in quoted_mints {
+                if !account_keys.contains(&Some(mint)) {
+                    violations.push(Violation::MintNotReferenced(mint));
+                }
+            }
+        }
+    }
+
+    /// The output of the swap must reach the user associated token account of the output mint
+    /// or the destination token account, the inner instructions of the router are not decoded
+    fn verify_jupiter_instruction(
+        &self,
+        index: usize,
+        instruction: &ResolvedInstruction,
+        output_mint: &Pubkey,
+        violations: &mut Vec<Violation>,
+    ) {
+        let discriminator = instruction
+            .data
+            .get(..8)
+            .and_then(|discriminator| <[u8; 8]>::try_from(discriminator).ok());
+        let destination = match discriminator {
+            // Token program, user transfer authority, user source, user destination then the optional
+            // destination token account, the Jupiter program when it is not set
+            Some(JUPITER_ROUTE | JUPITER_ROUTE_WITH_TOKEN_LEDGER | JUPITER_EXACT_OUT_ROUTE) => {
+                match instruction.account(4) {
+                    Some(JUPITER_PROGRAM_ID) => instruction.account(3),
+                    destination => destination,
+                }
+            }
+            // Token program, program authority, user transfer authority, source, program source,
+            // program destination then the destination token account
+            Some(
+                JUPITER_SHARED_ACCOUNTS_ROUTE
+                | JUPITER_SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER
+                | JUPITER_SHARED_ACCOUNTS_EXACT_OUT_ROUTE,
+            ) => instruction.account(6),
+            Some(JUPITER_CREATE_TOKEN_LEDGER | JUPITER_SET_TOKEN_LEDGER) => return,
+            _ => {
+                violations.push(Violation::InstructionNotAllowed {
+                    index,
+                    program_id: instruction.program_id,
+                });
+                return;
+            }
+        };
+        let Some(destination) = destination else {
+            return;
+        };
+        let is_user_destination = self.destination_token_account == Some(destination)
+            || [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]
+                .iter()
+                .any(|token_program| {
+                    associated_token_address(&self.user, output_mint, token_program) == destination
+                });
+        if !is_user_destination {
+            violations.push(Violation::UnexpectedSwapDestination { index, destination });
+        }
+    }
+
+    fn verify_token_instruction(
+        &self,
+        index: usize,
+        instruction: &ResolvedInstruction,
+        is_user_destination: &impl Fn(&Pubkey) -> bool,
+        violations: &mut Vec<Violation>,
+    ) {
+        let mut check_destination = |position: usize| {
+            if let Some(destination) = instruction.account(position) {
+                if !is_user_destination(&destination) {
+                    violations.push(Violation::UnexpectedTokenTransfer { index, destination });
+                }
+            }
+        };
+        match instruction.data.first().copied() {
+            // Transfer: source, destination
+            Some(TOKEN_TRANSFER) => check_destination(1),
+            // Transfer checked and transfer checked with fee: source, mint, destination
+            Some(TOKEN_TRANSFER_CHECKED) => check_destination(2),
+            Some(TOKEN_2022_TRANSFER_FEE_EXTENSION)
+                if instruction.program_id == TOKEN_2022_PROGRAM_ID
+                    && instruction.data.get(1) == Some(&TOKEN_2022_TRANSFER_CHECKED_WITH_FEE) =>
+            {
+                check_destination(2)
+            }
+            Some(TOKEN_APPROVE) | Some(TOKEN_APPROVE_CHECKED) => {
+                // Approve: source, delegate; approve checked: source, mint, delegate
+                let position = if instruction.data[0] == TOKEN_APPROVE {
+                    1
+                } else {
+                    2
+                };
+                if let Some(delegate) = instruction.account(position) {
+                    if !self.allowed_destinations.contains(&delegate) {
+                        violations.push(Violation::UnexpectedApproval { index, delegate });
+                    }
+                }
+            }
+            Some(TOKEN_SET_AUTHORITY) => {
+                // Tag, authority type, option tag then the new authority
+                let new_authority = match instruction.data.get(2) {
+                    Some(1) => match instruction
+                        .data
+                        .get(3..35)
+                        .and_then(|authority| Pubkey::try_from(authority).ok())
+                    {
+                        Some(authority) => Some(authority),
+                        None => {
+                            violations.push(Violation::UndecodableInstruction { index });
+                            return;
+                        }
+                    },
+                    _ => None,
+                };
+                if new_authority != Some(self.user) {
+                    violations.push(Violation::ForeignAuthority {
+                        index,
+                        account: instruction.account(0).unwrap_or_default(),
+                        new_authority,
+                    });
+                }
+            }
+            Some(TOKEN_CLOSE_ACCOUNT) => {
+                if let Some(destination) = instruction.account(1) {
+                    if destination != self.user {
+                        violations.push(Violation::ForeignCloseDestination {
+                            index,
+                            account: instruction.account(0).unwrap_or_default(),
+                            destination,
+                        });
+                    }
+                }
+            }
+            Some(
+                tag @ (TOKEN_INITIALIZE_ACCOUNT
+                | TOKEN_INITIALIZE_ACCOUNT_2
+                | TOKEN_INITIALIZE_ACCOUNT_3),
+            ) => {
+                // Initialize account: account, mint, owner; the others have the owner after the tag
+                let owner = if tag == TOKEN_INITIALIZE_ACCOUNT {
+                    instruction.account(2)
+                } else {
+                    match instruction
+                        .data
+                        .get(1..33)
+                        .and_then(|owner| Pubkey::try_from(owner).ok())
+                    {
+                        Some(owner) => Some(owner),
+                        None => {
+                            violations.push(Violation::UndecodableInstruction { index });
+                            return;
+                        }
+                    }
+                };
+                if let Some(owner) = owner {
+                    if owner != self.user {
+                        violations.push(Violation::ForeignAuthority {
+                            index,
+                            account: instruction.account(0).unwrap_or_default(),
+                            new_authority: Some(owner),
+                        });
+                    }
+                }
+            }
+            Some(TOKEN_REVOKE)
+            | Some(TOKEN_SYNC_NATIVE)
+            | Some(TOKEN_INITIALIZE_IMMUTABLE_OWNER) => {}
+            // Burns, mints, freezes and the other extensions are not expected in a swap
+            _ => violations.push(Violation::InstructionNotAllowed {
+                index,
+                program_id: instruction.program_id,
+            }),
+        }
+    }
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::USDC_MINT;
+use jupiter_swap_api_client::{
+    accounts::mint_decimals,
+    amount::{AmountError, TokenAmount, UiAmountError},
+    quote::{QuoteRequest, QuoteRequestError, SwapMode},
+    verify::NATIVE_MINT,
+};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+
+fn decimals() -> HashMap<Pubkey, u8> {
+    HashMap::from([(USDC_MINT, 6), (NATIVE_MINT, 9)])
+}
+
+fn mint_data(decimals: u8) -> Vec<u8> {
+    let mut data = vec![0; 82];
+    data[44] = decimals;
+    data[45] = 1;
+    data
+}
+
+#[test]
+fn decimal_amounts_round_trip_exactly() {
+    for (raw, decimals) in [
+        (0, 0),
+        (1, 9),
+        (1_500_000, 6),
+        (u64::MAX, 0),
+        (u64::MAX, 19),
+        (123, 28),
+    ] {
+        let amount = TokenAmount::new(raw, decimals);
+        let ui_amount = amount.to_decimal().unwrap();
+        assert_eq!(TokenAmount::from_decimal(ui_amount, decimals), Ok(amount));
+    }
+
+    assert_eq!(
+        TokenAmount::new(1_500_000, 6).to_decimal(),
+        Ok(Decimal::new(15, 1))
+    );
+    // Trailing zeros beyond the decimals of the mint are exact
+    assert_eq!(
+        TokenAmount::from_decimal(Decimal::new(1_500_000_000, 9), 6),
+        Ok(TokenAmount::new(1_500_000, 6))
+    );
+    assert_eq!(TokenAmount::new(1_500_000, 6).to_string(), "1.500000");
+}
+
+#[test]
+fn invalid_decimal_amounts_are_rejected() {
+    let amount = Decimal::new(1_000_001, 7);
+    assert_eq!(
+        TokenAmount::from_decimal(amount, 6),
+        Err(AmountError::Inexact {
+            amount,
+            decimals: 6
+        })
+    );
+
+    assert_eq!(
+        TokenAmount::from_decimal(Decimal::ONE, 29),
+        Err(AmountError::TooManyDecimals(29))
+    );
+    assert_eq!(
+        TokenAmount::new(1, 29).to_decimal(),
+        Err(AmountError::TooManyDecimals(29))
+    );
+
+    // u64::MAX + 1 base units
+    let amount = Decimal::from(u64::MAX) + Decimal::ONE;
+    assert_eq!(
+        TokenAmount::from_decimal(amount, 0),
+        Err(AmountError::Overflow(amount))
+    );
+    // Overflows the decimal itself once scaled
+    assert_eq!(
+        TokenAmount::from_decimal(Decimal::MAX, 6),
+        Err(AmountError::Overflow(Decimal::MAX))
+    );
+
+    assert_eq!(
+        TokenAmount::from_decimal(Decimal::NEGATIVE_ONE, 6),
+        Err(AmountError::Negative(Decimal::NEGATIVE_ONE))
+    );
+    // Negative zero is zero
+    assert_eq!(
+        TokenAmount::from_decimal(-Decimal::ZERO, 6),
+        Ok(TokenAmount::new(0, 6))
+    );
+}
+
+#[test]
+fn ui_amount_uses_the_decimals_of_the_swap_mode_mint() {
+    let ui_amount = Decimal::new(15, 1);
+
+    let exact_in = QuoteRequest::from_ui_amount(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactIn,
+        ui_amount,
+        &decimals(),
+    )
+    .unwrap()
+    .slippage_bps(50)
+    .build()
+    .unwrap();
+    assert_eq!(exact_in.amount, 1_500_000);
+    assert_eq!(exact_in.slippage_bps, 50);
+
+    let exact_out = QuoteRequest::from_ui_amount(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactOut,
+        ui_amount,
+        &decimals(),
+    )
+    .unwrap()
+    .build()
+    .unwrap();
+    assert_eq!(exact_out.amount, 1_500_000_000);
+    assert_eq!(exact_out.swap_mode, Some(SwapMode::ExactOut));
+}
+
+#[test]
+fn ui_amount_request_is_validated_by_the_builder() {
+    let builder = QuoteRequest::from_ui_amount(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactIn,
+        Decimal::ZERO,
+        &decimals(),
+    )
+    .unwrap();
+    assert_eq!(builder.build().unwrap_err(), QuoteRequestError::ZeroAmount);
+
+    let builder = QuoteRequest::from_ui_amount(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactIn,
+        Decimal::ONE,
+        &decimals(),
+    )
+    .unwrap()
+    .slippage_bps(10_001);
+    assert_eq!(
+        builder.build().unwrap_err(),
+        QuoteRequestError::SlippageTooHigh(10_001)
+    );
+}
+
+#[test]
+fn ui_amount_requires_the_decimals_of_the_mint() {
+    let error = QuoteRequest::from_ui_amount(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactOut,
+        Decimal::ONE,
+        &HashMap::from([(USDC_MINT, 6)]),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        UiAmountError::Amount(AmountError::MissingDecimals(mint)) if mint == NATIVE_MINT
+    ));
+}
+
+#[tokio::test]
+async fn ui_amount_decimals_are_fetched_from_the_mint() {
+    let account_fetcher = HashMap::from([(NATIVE_MINT, mint_data(9)), (USDC_MINT, vec![0; 10])]);
+
+    let quote_request = QuoteRequest::from_ui_amount_fetched(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactOut,
+        Decimal::new(15, 1),
+        &account_fetcher,
+    )
+    .await
+    .unwrap()
+    .build()
+    .unwrap();
+    assert_eq!(quote_request.amount, 1_500_000_000);
+
+    let error = QuoteRequest::from_ui_amount_fetched(
+        USDC_MINT,
+        NATIVE_MINT,
+        SwapMode::ExactIn,
+        Decimal::ONE,
+        &account_fetcher,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(error, UiAmountError::InvalidMint(mint) if mint == USDC_MINT));
+
+    let error = QuoteRequest::from_ui_amount_fetched(
+        NATIVE_MINT,
+        USDC_MINT,
+        SwapMode::ExactOut,
+        Decimal::ONE,
+        &HashMap::new(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(error, UiAmountError::MintNotFound(mint) if mint == USDC_MINT));
+}
+
+#[test]
+fn mint_decimals_require_an_initialized_mint() {
+    assert_eq!(mint_decimals(&mint_data(6)), Some(6));
+
+    // Token 2022 mints with extensions are longer
+    let mut data = mint_data(9);
+    data.resize(200, 0);
+    assert_eq!(mint_decimals(&data), Some(9));
+
+    assert_eq!(mint_decimals(&mint_data(6)[..81]), None);
+    assert_eq!(mint_decimals(&[]), None);
+
+    let mut data = mint_data(6);
+    data[45] = 0;
+    assert_eq!(mint_decimals(&data), None);
+}
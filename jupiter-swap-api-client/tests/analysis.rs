@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use jupiter_swap_api_client::{
+    analysis::{LpFee, PlatformFeeShare},
+    quote::{PlatformFee, QuoteResponse, SwapMode},
+    route_plan_with_metadata::{RoutePlanStep, SwapInfo},
+};
+use rust_decimal::Decimal;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+const SOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+const USDC: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const USDT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+
+fn decimals() -> HashMap<Pubkey, u8> {
+    HashMap::from([(SOL, 9), (USDC, 6)])
+}
+
+fn step(
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    fee_amount: u64,
+    fee_mint: Pubkey,
+) -> RoutePlanStep {
+    RoutePlanStep {
+        swap_info: SwapInfo {
+            amm_key: Pubkey::new_unique(),
+            label: "Whirlpool".to_string(),
+            input_mint,
+            output_mint,
+            in_amount: 0,
+            out_amount: 0,
+            fee_amount,
+            fee_mint,
+        },
+        percent: 100,
+    }
+}
+
+fn quote_response(
+    swap_mode: SwapMode,
+    (input_mint, in_amount): (Pubkey, u64),
+    (output_mint, out_amount): (Pubkey, u64),
+    other_amount_threshold: u64,
+) -> QuoteResponse {
+    QuoteResponse {
+        input_mint,
+        in_amount,
+        output_mint,
+        out_amount,
+        other_amount_threshold,
+        swap_mode,
+        slippage_bps: 50,
+        computed_auto_slippage: None,
+        uses_quote_minimizing_slippage: None,
+        platform_fee: None,
+        price_impact_pct: Decimal::new(12, 4),
+        route_plan: Vec::new(),
+        context_slot: 0,
+        time_taken: 0.0,
+    }
+}
+
+#[test]
+fn exact_in_quote_is_analyzed_at_the_min_out_amount() {
+    // 2 SOL for 300 USDC, at least 298.5 USDC
+    let mut quote_response = quote_response(
+        SwapMode::ExactIn,
+        (SOL, 2_000_000_000),
+        (USDC, 300_000_000),
+        298_500_000,
+    );
+    quote_response.route_plan = vec![
+        step(SOL, USDT, 3_000_000, SOL),
+        step(USDT, USDC, 150_000, USDC),
+        step(SOL, USDC, 1_000_000, SOL),
+    ];
+    quote_response.platform_fee = Some(PlatformFee {
+        amount: 600_000,
+        fee_bps: 20,
+    });
+
+    let analysis = quote_response.analyze(&decimals()).unwrap();
+
+    assert_eq!(analysis.in_amount, Decimal::from(2));
+    assert_eq!(analysis.out_amount, Decimal::from(300));
+    // 300 / 2
+    assert_eq!(analysis.effective_price, Decimal::from(150));
+    // 298.5 / 2
+    assert_eq!(analysis.worst_case_price, Decimal::new(14925, 2));
+    // (150 - 149.25) / 150
+    assert_eq!(analysis.worst_case_deviation(), Decimal::new(5, 3));
+    // 0.003 + 0.001 SOL and 0.15 USDC, sorted by mint bytes
+    assert_eq!(
+        analysis.lp_fees,
+        vec![
+            LpFee {
+                mint: SOL,
+                amount: 4_000_000,
+                ui_amount: Some(Decimal::new(4, 3)),
+            },
+            LpFee {
+                mint: USDC,
+                amount: 150_000,
+                ui_amount: Some(Decimal::new(15, 2)),
+            },
+        ]
+    );
+    // 0.6 USDC over the 300 USDC out amount
+    assert_eq!(
+        analysis.platform_fee,
+        Some(PlatformFeeShare {
+            mint: USDC,
+            amount: 600_000,
+            ui_amount: Decimal::new(6, 1),
+            fee_bps: 20,
+            share: Decimal::new(2, 3),
+        })
+    );
+    assert!(analysis.is_price_impact_within(Decimal::new(12, 4)));
+    assert!(analysis.check_price_impact(Decimal::new(1, 4)).is_err());
+}
+
+#[test]
+fn exact_out_quote_is_analyzed_at_the_max_in_amount() {
+    // 160 USDC for 1 SOL, at most 200 USDC
+    let mut quote_response = quote_response(
+        SwapMode::ExactOut,
+        (USDC, 160_000_000),
+        (SOL, 1_000_000_000),
+        200_000_000,
+    );
+    quote_response.route_plan = vec![
+        step(USDC, USDT, 100_000, USDC),
+        step(USDT, SOL, 2_500, USDT),
+    ];
+    quote_response.platform_fee = Some(PlatformFee {
+        amount: 320_000,
+        fee_bps: 20,
+    });
+
+    let analysis = quote_response.analyze(&decimals()).unwrap();
+
+    // 1 / 160
+    assert_eq!(analysis.effective_price, Decimal::new(625, 5));
+    // 1 / 200
+    assert_eq!(analysis.worst_case_price, Decimal::new(5, 3));
+    // (0.00625 - 0.005) / 0.00625
+    assert_eq!(analysis.worst_case_deviation(), Decimal::new(2, 1));
+    // The decimals of USDT are unknown
+    assert_eq!(
+        analysis.lp_fees,
+        vec![
+            LpFee {
+                mint: USDC,
+                amount: 100_000,
+                ui_amount: Some(Decimal::new(1, 1)),
+            },
+            LpFee {
+                mint: USDT,
+                amount: 2_500,
+                ui_amount: None,
+            },
+        ]
+    );
+    // 0.32 USDC over the 160 USDC in amount
+    assert_eq!(
+        analysis.platform_fee,
+        Some(PlatformFeeShare {
+            mint: USDC,
+            amount: 320_000,
+            ui_amount: Decimal::new(32, 2),
+            fee_bps: 20,
+            share: Decimal::new(2, 3),
+        })
+    );
+}
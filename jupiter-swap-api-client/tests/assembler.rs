@@ -0,0 +1,111 @@
+mod common;
+
+use std::{collections::HashMap, convert::Infallible};
+
+use common::{lookup_table_data, swap_instructions};
+use jupiter_swap_api_client::{
+    accounts::AccountFetcher,
+    assembler::{AssembleError, TransactionAssembler},
+};
+use solana_sdk::{hash::Hash, pubkey::Pubkey};
+
+#[tokio::test]
+async fn swap_instructions_are_assembled_in_order_with_the_lookup_tables() {
+    let payer = Pubkey::new_unique();
+    let lookup_table = Pubkey::new_unique();
+    let amm_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut swap_instructions = swap_instructions(&payer, &amm_accounts);
+    swap_instructions.address_lookup_table_addresses = vec![lookup_table];
+    let account_fetcher = HashMap::from([(
+        lookup_table,
+        lookup_table_data(&[Pubkey::new_unique(), amm_accounts[1], amm_accounts[0]]),
+    )]);
+
+    let transaction = TransactionAssembler::new(account_fetcher)
+        .assemble(&swap_instructions, &payer, Hash::new_unique())
+        .await
+        .unwrap();
+
+    let message = &transaction.message;
+    let static_keys = message.static_account_keys();
+    assert_eq!(transaction.signatures.len(), 1);
+    assert_eq!(static_keys[0], payer);
+    assert_eq!(
+        message
+            .instructions()
+            .iter()
+            .map(|compiled| (*compiled.program_id(static_keys), compiled.data.clone()))
+            .collect::<Vec<_>>(),
+        swap_instructions
+            .instructions()
+            .map(|(_, instruction)| (instruction.program_id, instruction.data.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    // The AMM accounts are only referenced through the lookup table
+    let lookups = message.address_table_lookups().unwrap();
+    assert_eq!(lookups.len(), 1);
+    assert_eq!(lookups[0].account_key, lookup_table);
+    assert_eq!(lookups[0].writable_indexes, vec![2, 1]);
+    assert!(lookups[0].readonly_indexes.is_empty());
+    assert!(amm_accounts
+        .iter()
+        .all(|account| !static_keys.contains(account)));
+}
+
+#[tokio::test]
+async fn missing_lookup_table_is_rejected() {
+    let payer = Pubkey::new_unique();
+    let lookup_table = Pubkey::new_unique();
+    let mut swap_instructions = swap_instructions(&payer, &[]);
+    swap_instructions.address_lookup_table_addresses = vec![lookup_table];
+
+    let error = TransactionAssembler::new(HashMap::new())
+        .assemble(&swap_instructions, &payer, Hash::new_unique())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, AssembleError::LookupTableNotFound(address) if address == lookup_table)
+    );
+}
+
+/// Fetcher dropping the last requested account
+struct TruncatingFetcher(HashMap<Pubkey, Vec<u8>>);
+
+impl AccountFetcher for TruncatingFetcher {
+    type Error = Infallible;
+
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        let mut accounts = self.0.get_multiple_accounts(pubkeys).await?;
+        accounts.pop();
+        Ok(accounts)
+    }
+}
+
+#[tokio::test]
+async fn fetcher_must_return_every_lookup_table() {
+    let addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let account_fetcher = TruncatingFetcher(
+        addresses
+            .iter()
+            .map(|address| (*address, lookup_table_data(&[Pubkey::new_unique()])))
+            .collect(),
+    );
+
+    let error = TransactionAssembler::new(account_fetcher)
+        .fetch_address_lookup_tables(&addresses)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        AssembleError::AccountCountMismatch {
+            expected: 2,
+            returned: 1
+        }
+    ));
+}
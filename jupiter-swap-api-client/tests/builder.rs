@@ -0,0 +1,125 @@
+mod common;
+
+use common::{
+    mock_server::{fast_policy, response, MockServer},
+    swap_request, USDC_MINT, USER,
+};
+use jupiter_swap_api_client::{
+    quote::QuoteRequest, retry::RetryPolicy, verify::NATIVE_MINT, JupiterSwapApiClient,
+};
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Client,
+};
+
+const LABELS: &str = r#"{"JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4":"Jupiter"}"#;
+
+fn quote_request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: USDC_MINT,
+        output_mint: NATIVE_MINT,
+        amount: 1_000_000,
+        slippage_bps: 50,
+        ..QuoteRequest::default()
+    }
+}
+
+fn targets(server: &MockServer) -> Vec<String> {
+    server
+        .requests()
+        .iter()
+        .map(|request| request.target().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn api_key_and_default_headers_are_sent() {
+    let server = MockServer::start(vec![response(200, &[], LABELS)]).await;
+    let client = server
+        .client_builder()
+        .api_key("secret")
+        .default_header(
+            HeaderName::from_static("x-client"),
+            HeaderValue::from_static("bot"),
+        )
+        .user_agent("swapper/1.0")
+        .build()
+        .unwrap();
+
+    client.program_id_to_label().await.unwrap();
+    client.prices(&[USDC_MINT]).await.unwrap_err();
+
+    for request in server.requests() {
+        assert_eq!(request.header("x-api-key"), Some("secret"));
+        assert_eq!(request.header("x-client"), Some("bot"));
+        assert_eq!(request.header("user-agent"), Some("swapper/1.0"));
+    }
+    assert_eq!(server.request_count(), 2);
+}
+
+#[tokio::test]
+async fn api_key_is_sent_with_a_custom_http_client() {
+    let server = MockServer::start(vec![response(200, &[], LABELS)]).await;
+    let client = server
+        .client_builder()
+        .api_key("secret")
+        // Left to the custom client
+        .default_header(
+            HeaderName::from_static("x-client"),
+            HeaderValue::from_static("bot"),
+        )
+        .http_client(Client::new())
+        .build()
+        .unwrap();
+
+    client.program_id_to_label().await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("x-api-key"), Some("secret"));
+    assert_eq!(request.header("x-client"), None);
+}
+
+#[tokio::test]
+async fn paths_are_joined_to_the_trimmed_base_path() {
+    let server = MockServer::start(vec![response(200, &[], LABELS)]).await;
+    let client = JupiterSwapApiClient::builder(format!("{}/swap/v1/", server.url))
+        .price_base_path(format!("{}/price/v3/", server.url))
+        .retry_policy(fast_policy())
+        .build()
+        .unwrap();
+
+    client.program_id_to_label().await.unwrap();
+    client.quote(quote_request()).await.unwrap_err();
+    client.prices(&[USDC_MINT]).await.unwrap_err();
+
+    let targets = targets(&server);
+    assert_eq!(targets[0], "/swap/v1/program-id-to-label");
+    assert!(targets[1].starts_with("/swap/v1/quote?inputMint="));
+    assert!(targets[2].starts_with("/price/v3?ids="));
+}
+
+#[tokio::test]
+async fn path_overrides_are_used() {
+    let server = MockServer::start(vec![response(200, &[], LABELS)]).await;
+    let client = JupiterSwapApiClient::builder(server.url.clone())
+        .quote_path("/v2/quote")
+        .swap_path("/v2/swap")
+        .swap_instructions_path("/v2/swap-instructions")
+        .program_id_to_label_path("/v2/labels")
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    client.quote(quote_request()).await.unwrap_err();
+    let swap_request = swap_request(USER);
+    client.swap(&swap_request, None).await.unwrap_err();
+    client.swap_instructions(&swap_request).await.unwrap_err();
+    client.program_id_to_label().await.unwrap();
+
+    let targets = targets(&server);
+    assert!(targets[0].starts_with("/v2/quote?"));
+    assert_eq!(
+        targets[1..],
+        ["/v2/swap", "/v2/swap-instructions", "/v2/labels"]
+    );
+}
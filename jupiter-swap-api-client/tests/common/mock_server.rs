@@ -0,0 +1,149 @@
+//! HTTP server answering with scripted responses, recording the requests received
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use jupiter_swap_api_client::{
+    retry::RetryPolicy, JupiterSwapApiClient, JupiterSwapApiClientBuilder,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    /// e.g. `GET /program-id-to-label HTTP/1.1`
+    pub request_line: String,
+    /// Names are lowercase
+    pub headers: Vec<(String, String)>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Path and query of the request line
+    pub fn target(&self) -> &str {
+        self.request_line.split(' ').nth(1).unwrap_or_default()
+    }
+}
+
+/// Answers with the scripted responses in order, the last one repeating
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub async fn start(responses: Vec<String>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for count in 0.. {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut stream).await;
+                received.lock().unwrap().push(request);
+                let response = &responses[count.min(responses.len() - 1)];
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+        Self { url, requests }
+    }
+
+    /// Builder with every API served by the server, under `/tokens`, `/price`, `/ultra`,
+    /// `/trigger` and `/recurring` for the APIs with their own base path
+    pub fn client_builder(&self) -> JupiterSwapApiClientBuilder {
+        JupiterSwapApiClient::builder(self.url.clone())
+            .tokens_base_path(format!("{}/tokens", self.url))
+            .price_base_path(format!("{}/price", self.url))
+            .ultra_base_path(format!("{}/ultra", self.url))
+            .trigger_base_path(format!("{}/trigger", self.url))
+            .recurring_base_path(format!("{}/recurring", self.url))
+            .retry_policy(fast_policy())
+    }
+
+    pub fn client(&self) -> JupiterSwapApiClient {
+        self.client_builder().build().unwrap()
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn request_lines(&self) -> Vec<String> {
+        self.requests()
+            .into_iter()
+            .map(|request| request.request_line)
+            .collect()
+    }
+
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+/// Reads the headers and the body of the request
+async fn read_request(stream: &mut TcpStream) -> RecordedRequest {
+    let mut received = Vec::new();
+    let mut buffer = [0; 4096];
+    loop {
+        let read = stream.read(&mut buffer).await.unwrap();
+        received.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&received).to_string();
+        if let Some(headers_end) = text.find("\r\n\r\n") {
+            let mut lines = text[..headers_end].lines();
+            let request_line = lines.next().unwrap_or_default().to_string();
+            let headers = lines
+                .filter_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    Some((name.trim().to_ascii_lowercase(), value.trim().to_string()))
+                })
+                .collect::<Vec<_>>();
+            let content_length = headers
+                .iter()
+                .find(|(name, _)| name == "content-length")
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+                .unwrap_or(0);
+            if read == 0 || received.len() >= headers_end + 4 + content_length {
+                return RecordedRequest {
+                    request_line,
+                    headers,
+                };
+            }
+        }
+    }
+}
+
+pub fn response(status: u16, headers: &[&str], body: &str) -> String {
+    let mut response = format!(
+        "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    for header in headers {
+        response.push_str(header);
+        response.push_str("\r\n");
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    response
+}
+
+/// Default policy without the waits
+pub fn fast_policy() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(1),
+        jitter: false,
+        ..RetryPolicy::default()
+    }
+}
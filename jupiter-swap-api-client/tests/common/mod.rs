@@ -0,0 +1,105 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+
+pub mod mock_server;
+
+use std::borrow::Cow;
+
+use jupiter_swap_api_client::{
+    quote::{QuoteResponse, SwapMode},
+    swap::{SwapInstructionsResponse, SwapRequest},
+    transaction_config::TransactionConfig,
+    verify::{JUPITER_PROGRAM_ID, NATIVE_MINT},
+};
+use rust_decimal::Decimal;
+use solana_sdk::{
+    address_lookup_table::state::{AddressLookupTable, LookupTableMeta},
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    system_instruction,
+};
+
+pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+pub const USER: Pubkey = pubkey!("2AQdpHJ2JpcEgPiATUXjQxA8QmafFegfQwSLWSprPicm");
+
+/// ExactIn quote of 1 USDC for 0.005 SOL with a 50 bps slippage
+pub fn quote_response() -> QuoteResponse {
+    QuoteResponse {
+        input_mint: USDC_MINT,
+        in_amount: 1_000_000,
+        output_mint: NATIVE_MINT,
+        out_amount: 5_000_000,
+        other_amount_threshold: 4_975_000,
+        swap_mode: SwapMode::ExactIn,
+        slippage_bps: 50,
+        computed_auto_slippage: None,
+        uses_quote_minimizing_slippage: None,
+        platform_fee: None,
+        price_impact_pct: Decimal::ZERO,
+        route_plan: Vec::new(),
+        context_slot: 0,
+        time_taken: 0.0,
+    }
+}
+
+pub fn swap_request(user_public_key: Pubkey) -> SwapRequest {
+    SwapRequest {
+        user_public_key,
+        quote_response: quote_response(),
+        config: TransactionConfig::default(),
+    }
+}
+
+/// Instruction loading `accounts` as writable, told apart by its one byte of data
+pub fn instruction(program_id: Pubkey, data: u8, accounts: &[Pubkey]) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id,
+        &[data],
+        accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false))
+            .collect(),
+    )
+}
+
+/// Swap instructions with one instruction or more of each kind and a tip paid by `payer`,
+/// the swap instruction loads `amm_accounts`
+pub fn swap_instructions(payer: &Pubkey, amm_accounts: &[Pubkey]) -> SwapInstructionsResponse {
+    let setup_program = Pubkey::new_unique();
+    SwapInstructionsResponse {
+        token_ledger_instruction: Some(instruction(JUPITER_PROGRAM_ID, 1, &[])),
+        compute_budget_instructions: vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000),
+        ],
+        setup_instructions: vec![
+            instruction(setup_program, 2, &[]),
+            instruction(setup_program, 3, &[]),
+        ],
+        swap_instruction: instruction(JUPITER_PROGRAM_ID, 4, amm_accounts),
+        cleanup_instruction: Some(instruction(setup_program, 5, &[])),
+        other_instructions: vec![system_instruction::transfer(
+            payer,
+            &Pubkey::new_unique(),
+            1_000,
+        )],
+        address_lookup_table_addresses: Vec::new(),
+        prioritization_fee_lamports: 300,
+        compute_unit_limit: 300_000,
+        prioritization_type: None,
+        dynamic_slippage_report: None,
+        simulation_error: None,
+    }
+}
+
+/// Account data of an address lookup table holding `addresses`
+pub fn lookup_table_data(addresses: &[Pubkey]) -> Vec<u8> {
+    AddressLookupTable {
+        meta: LookupTableMeta::default(),
+        addresses: Cow::Borrowed(addresses),
+    }
+    .serialize_for_tests()
+    .unwrap()
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{instruction, lookup_table_data, swap_instructions};
+use jupiter_swap_api_client::{
+    assembler::{AssembleError, TransactionAssembler},
+    estimate::TransactionEstimate,
+};
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::{self, ComputeBudgetInstruction},
+    hash::Hash,
+    instruction::Instruction,
+    message::v0::MessageAddressTableLookup,
+    pubkey,
+    pubkey::Pubkey,
+    system_instruction,
+};
+
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[test]
+fn caller_instructions_are_inserted_around_the_swap() {
+    let payer = Pubkey::new_unique();
+    let swap_instructions = swap_instructions(&payer, &[]);
+    let memo = Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"order 42", Vec::new());
+    let tip = system_instruction::transfer(&payer, &Pubkey::new_unique(), 10_000);
+
+    let composed_swap = swap_instructions
+        .compose()
+        .before_setup(memo.clone())
+        .before_setup(ComputeBudgetInstruction::set_compute_unit_price(50_000))
+        .after_cleanup(tip.clone());
+    let instructions = composed_swap.instructions();
+
+    // The caller compute unit price replaces the one of the swap in its position
+    assert_eq!(
+        instructions,
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+            ComputeBudgetInstruction::set_compute_unit_price(50_000),
+            swap_instructions.token_ledger_instruction.clone().unwrap(),
+            memo,
+            swap_instructions.setup_instructions[0].clone(),
+            swap_instructions.setup_instructions[1].clone(),
+            swap_instructions.swap_instruction.clone(),
+            swap_instructions.cleanup_instruction.clone().unwrap(),
+            tip,
+            swap_instructions.other_instructions[0].clone(),
+        ]
+    );
+    let compute_budget_kinds = instructions
+        .iter()
+        .filter(|instruction| instruction.program_id == compute_budget::id())
+        .map(|instruction| instruction.data[0])
+        .collect::<Vec<_>>();
+    assert_eq!(compute_budget_kinds.len(), 2);
+    assert_ne!(compute_budget_kinds[0], compute_budget_kinds[1]);
+
+    let composed_transaction = composed_swap
+        .compile(&payer, Hash::new_unique(), &[])
+        .unwrap();
+    assert_eq!(
+        composed_transaction
+            .transaction
+            .message
+            .instructions()
+            .len(),
+        instructions.len()
+    );
+    assert!(composed_transaction.fits_in_packet());
+}
+
+/// Lookups of the message by table, with the indexes of the writable accounts
+fn writable_lookups(lookups: &[MessageAddressTableLookup]) -> HashMap<Pubkey, Vec<u8>> {
+    lookups
+        .iter()
+        .map(|lookup| {
+            assert!(lookup.readonly_indexes.is_empty());
+            (lookup.account_key, lookup.writable_indexes.clone())
+        })
+        .collect()
+}
+
+#[test]
+fn lookup_tables_are_merged_across_sources() {
+    let payer = Pubkey::new_unique();
+    let amm_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let caller_account = Pubkey::new_unique();
+    let swap_table = Pubkey::new_unique();
+    let caller_table = Pubkey::new_unique();
+    let mut swap_instructions = swap_instructions(&payer, &amm_accounts);
+    swap_instructions.address_lookup_table_addresses = vec![swap_table];
+
+    let composed_swap = swap_instructions
+        .compose()
+        .after_swap(instruction(MEMO_PROGRAM_ID, 7, &[caller_account]))
+        .address_lookup_table(caller_table)
+        .address_lookup_table(caller_table);
+    assert_eq!(
+        composed_swap.address_lookup_table_addresses(),
+        [swap_table, caller_table]
+    );
+
+    // The table resolved by the caller is no longer fetched and wins over the compile tables
+    let composed_swap = composed_swap.address_lookup_table_account(AddressLookupTableAccount {
+        key: swap_table,
+        addresses: amm_accounts.to_vec(),
+    });
+    assert_eq!(
+        composed_swap.address_lookup_table_addresses(),
+        [caller_table]
+    );
+    let composed_transaction = composed_swap
+        .compile(
+            &payer,
+            Hash::new_unique(),
+            &[
+                AddressLookupTableAccount {
+                    key: swap_table,
+                    addresses: Vec::new(),
+                },
+                AddressLookupTableAccount {
+                    key: caller_table,
+                    addresses: vec![Pubkey::new_unique(), caller_account],
+                },
+            ],
+        )
+        .unwrap();
+
+    let message = &composed_transaction.transaction.message;
+    assert_eq!(
+        writable_lookups(message.address_table_lookups().unwrap()),
+        HashMap::from([(swap_table, vec![0, 1]), (caller_table, vec![1])])
+    );
+    let static_keys = message.static_account_keys();
+    assert!(!static_keys.contains(&caller_account));
+    assert!(amm_accounts
+        .iter()
+        .all(|account| !static_keys.contains(account)));
+    assert_eq!(composed_transaction.estimate.lookup_tables, 2);
+    assert_eq!(composed_transaction.estimate.lookup_table_accounts, 3);
+}
+
+#[tokio::test]
+async fn composed_swap_is_assembled_with_the_fetched_lookup_tables() {
+    let payer = Pubkey::new_unique();
+    let amm_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let caller_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let swap_table = Pubkey::new_unique();
+    let caller_table = Pubkey::new_unique();
+    let resolved_table = Pubkey::new_unique();
+    let mut swap_instructions = swap_instructions(&payer, &amm_accounts);
+    swap_instructions.address_lookup_table_addresses = vec![swap_table];
+    let memo = instruction(MEMO_PROGRAM_ID, 7, &caller_accounts);
+    let composed_swap = swap_instructions
+        .compose()
+        .before_swap(memo.clone())
+        .address_lookup_table(caller_table)
+        .address_lookup_table_account(AddressLookupTableAccount {
+            key: resolved_table,
+            addresses: vec![caller_accounts[1]],
+        });
+    // The resolved table is missing from the fetcher, fetching it would fail
+    let account_fetcher = HashMap::from([
+        (swap_table, lookup_table_data(&amm_accounts)),
+        (caller_table, lookup_table_data(&[caller_accounts[0]])),
+    ]);
+    let assembler = TransactionAssembler::new(account_fetcher);
+
+    let composed_transaction = assembler
+        .assemble_composed(&composed_swap, &payer, Hash::new_unique())
+        .await
+        .unwrap();
+
+    let transaction = &composed_transaction.transaction;
+    let static_keys = transaction.message.static_account_keys();
+    assert_eq!(static_keys[0], payer);
+    assert_eq!(
+        transaction
+            .message
+            .instructions()
+            .iter()
+            .map(|compiled| (*compiled.program_id(static_keys), compiled.data.clone()))
+            .collect::<Vec<_>>(),
+        composed_swap
+            .instructions()
+            .iter()
+            .map(|instruction| (instruction.program_id, instruction.data.clone()))
+            .collect::<Vec<_>>()
+    );
+    let swap_position = composed_swap
+        .instructions()
+        .iter()
+        .position(|instruction| *instruction == swap_instructions.swap_instruction)
+        .unwrap();
+    assert_eq!(composed_swap.instructions()[swap_position - 1], memo);
+    assert_eq!(
+        writable_lookups(transaction.message.address_table_lookups().unwrap()),
+        HashMap::from([
+            (swap_table, vec![0, 1]),
+            (caller_table, vec![0]),
+            (resolved_table, vec![0]),
+        ])
+    );
+    assert_eq!(
+        composed_transaction.estimate,
+        TransactionEstimate::from_transaction(transaction)
+    );
+
+    let missing_table = Pubkey::new_unique();
+    let error = assembler
+        .assemble_composed(
+            &composed_swap.clone().address_lookup_table(missing_table),
+            &payer,
+            Hash::new_unique(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AssembleError::LookupTableNotFound(table) if table == missing_table));
+}
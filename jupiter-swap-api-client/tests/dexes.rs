@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use jupiter_swap_api_client::dexes::{DexLabel, DexSet, UnsupportedDexes};
+use solana_sdk::pubkey::Pubkey;
+
+fn set(labels: &[DexLabel]) -> DexSet {
+    labels.iter().cloned().collect()
+}
+
+#[test]
+fn comma_separated_list_is_parsed_with_unknown_labels() {
+    let dexes: DexSet = " Whirlpool,Raydium CLMM ,,New Dex,Whirlpool"
+        .parse()
+        .unwrap();
+
+    assert_eq!(
+        dexes,
+        set(&[
+            DexLabel::Whirlpool,
+            DexLabel::RaydiumClmm,
+            DexLabel::Other("New Dex".to_string()),
+        ])
+    );
+    assert!("".parse::<DexSet>().unwrap().is_empty());
+
+    let deserialized: DexSet =
+        serde_json::from_str(r#""Saber (Decimals),Pump.fun Amm,Soon""#).unwrap();
+    assert_eq!(
+        deserialized,
+        set(&[
+            DexLabel::SaberDecimals,
+            DexLabel::PumpFunAmm,
+            DexLabel::Other("Soon".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn labels_round_trip_through_their_string() {
+    for label in [
+        DexLabel::MeteoraDammV2,
+        DexLabel::StabbleStableSwap,
+        DexLabel::Other("New Dex".to_string()),
+    ] {
+        assert_eq!(DexLabel::from(label.to_string()), label);
+        assert_eq!(label.as_str().parse::<DexLabel>().unwrap(), label);
+    }
+    // Labels are case sensitive, as returned by the API
+    assert_eq!(
+        DexLabel::from("whirlpool"),
+        DexLabel::Other("whirlpool".to_string())
+    );
+}
+
+#[test]
+fn serialization_is_sorted_and_deduplicated() {
+    let dexes = set(&[
+        DexLabel::Other("New Dex".to_string()),
+        DexLabel::Whirlpool,
+        DexLabel::MeteoraDlmm,
+        DexLabel::Whirlpool,
+        DexLabel::Aldrin,
+    ]);
+
+    assert_eq!(dexes.len(), 4);
+    // Known labels in declaration order, then the unknown ones
+    assert_eq!(dexes.to_string(), "Aldrin,Meteora DLMM,Whirlpool,New Dex");
+    assert_eq!(
+        serde_json::to_string(&dexes).unwrap(),
+        r#""Aldrin,Meteora DLMM,Whirlpool,New Dex""#
+    );
+    let round_trip: DexSet = dexes.to_string().parse().unwrap();
+    assert_eq!(round_trip, dexes);
+    assert_eq!(DexSet::new().to_string(), "");
+}
+
+#[test]
+fn set_operations() {
+    let left = set(&[DexLabel::Whirlpool, DexLabel::Raydium, DexLabel::Phoenix]);
+    let right = set(&[DexLabel::Raydium, DexLabel::Phoenix, DexLabel::SolFi]);
+
+    assert_eq!(
+        left.union(&right),
+        set(&[
+            DexLabel::Whirlpool,
+            DexLabel::Raydium,
+            DexLabel::Phoenix,
+            DexLabel::SolFi,
+        ])
+    );
+    assert_eq!(
+        left.intersection(&right),
+        set(&[DexLabel::Raydium, DexLabel::Phoenix])
+    );
+    assert_eq!(left.difference(&right), set(&[DexLabel::Whirlpool]));
+    assert_eq!(right.difference(&left), set(&[DexLabel::SolFi]));
+    assert!(!left.is_disjoint(&right));
+    assert!(left.is_disjoint(&set(&[DexLabel::SolFi])));
+}
+
+#[test]
+fn set_is_validated_against_the_label_map() {
+    let supported = DexSet::from_program_id_to_label(&HashMap::from([
+        (Pubkey::new_unique(), "Whirlpool".to_string()),
+        (Pubkey::new_unique(), "Whirlpool".to_string()),
+        (Pubkey::new_unique(), "New Dex".to_string()),
+    ]));
+    assert_eq!(
+        supported,
+        set(&[DexLabel::Whirlpool, DexLabel::Other("New Dex".to_string())])
+    );
+
+    assert_eq!(
+        set(&[DexLabel::Whirlpool, DexLabel::Other("New Dex".to_string())]).validate(&supported),
+        Ok(())
+    );
+    assert_eq!(DexSet::new().validate(&supported), Ok(()));
+    let error = set(&[
+        DexLabel::Whirlpool,
+        DexLabel::Phoenix,
+        DexLabel::Other("Gone".to_string()),
+    ])
+    .validate(&supported)
+    .unwrap_err();
+    assert_eq!(
+        error,
+        UnsupportedDexes(set(&[
+            DexLabel::Phoenix,
+            DexLabel::Other("Gone".to_string())
+        ]))
+    );
+    assert_eq!(
+        error.to_string(),
+        "Dexes not supported by the API: Phoenix,Gone"
+    );
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{
+    mock_server::{response, MockServer},
+    quote_response, USDC_MINT,
+};
+use jupiter_swap_api_client::{
+    error::{ErrorCode, JupiterApiError},
+    quote::QuoteRequest,
+    retry::RetryPolicy,
+    verify::NATIVE_MINT,
+    ClientError, JupiterSwapApiClient,
+};
+use reqwest::StatusCode;
+use tokio::net::TcpListener;
+
+fn quote_request() -> QuoteRequest {
+    QuoteRequest {
+        input_mint: USDC_MINT,
+        output_mint: NATIVE_MINT,
+        amount: 1_000_000,
+        slippage_bps: 50,
+        ..QuoteRequest::default()
+    }
+}
+
+#[tokio::test]
+async fn api_error_is_parsed_from_client_error_body() {
+    let body = r#"{"error":"Could not find any route","errorCode":"COULD_NOT_FIND_ANY_ROUTE"}"#;
+    let server = MockServer::start(vec![response(400, &[], body)]).await;
+
+    let error = server.client().quote(quote_request()).await.unwrap_err();
+
+    let ClientError::RequestFailed {
+        status,
+        error: api_error,
+        body: error_body,
+    } = &error
+    else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(*status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        *api_error,
+        Some(JupiterApiError {
+            error: "Could not find any route".to_string(),
+            error_code: Some(ErrorCode::CouldNotFindAnyRoute),
+        })
+    );
+    assert_eq!(error_body, body);
+    assert_eq!(error.error_code(), Some(&ErrorCode::CouldNotFindAnyRoute));
+    assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+    assert!(!error.is_retryable());
+    // Client errors are not retried
+    assert_eq!(server.request_count(), 1);
+}
+
+#[tokio::test]
+async fn unknown_error_codes_and_bodies_are_kept() {
+    let server = MockServer::start(vec![
+        response(
+            422,
+            &[],
+            r#"{"error":"Amount too small","errorCode":"AMOUNT_TOO_SMALL"}"#,
+        ),
+        response(400, &[], "Bad Request"),
+    ])
+    .await;
+    let client = server.client();
+
+    let error = client.quote(quote_request()).await.unwrap_err();
+    assert_eq!(
+        error.error_code(),
+        Some(&ErrorCode::Other("AMOUNT_TOO_SMALL".to_string()))
+    );
+    assert_eq!(error.error_code().unwrap().to_string(), "AMOUNT_TOO_SMALL");
+
+    let error = client.quote(quote_request()).await.unwrap_err();
+    assert!(error.api_error().is_none());
+    assert!(matches!(
+        error,
+        ClientError::RequestFailed { ref body, .. } if body == "Bad Request"
+    ));
+}
+
+#[tokio::test]
+async fn rate_limited_error_carries_retry_after() {
+    let body = r#"{"error":"Rate limit exceeded"}"#;
+    let server = MockServer::start(vec![response(429, &["Retry-After: 3"], body)]).await;
+
+    let error = server
+        .client()
+        .with_retry_policy(RetryPolicy::none())
+        .program_id_to_label()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::RateLimited { .. }));
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(3)));
+    assert_eq!(error.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+    assert_eq!(error.api_error().unwrap().error, "Rate limit exceeded");
+    assert!(error.is_retryable());
+    assert_eq!(
+        error.to_string(),
+        format!("Rate limited, retry after 3s: {body}")
+    );
+}
+
+#[tokio::test]
+async fn server_errors_are_retryable() {
+    let server = MockServer::start(vec![response(502, &[], "")]).await;
+
+    let error = server
+        .client()
+        .with_retry_policy(RetryPolicy::none())
+        .program_id_to_label()
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.status(), Some(StatusCode::BAD_GATEWAY));
+    assert!(error.retry_after().is_none());
+    assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn timeout_and_connection_errors_are_retryable() {
+    // Accepts connections without ever answering
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let silent_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut streams = Vec::new();
+        loop {
+            streams.push(listener.accept().await.unwrap());
+        }
+    });
+    let client = JupiterSwapApiClient::builder(silent_url)
+        .timeout(Duration::from_millis(50))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let error = client.program_id_to_label().await.unwrap_err();
+    assert!(matches!(error, ClientError::Timeout(_)), "{error:?}");
+    assert!(error.is_retryable());
+
+    // Nothing listens on the port once the listener is dropped
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let closed_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let client = JupiterSwapApiClient::builder(closed_url)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let error = client.program_id_to_label().await.unwrap_err();
+    assert!(matches!(error, ClientError::Transport(_)), "{error:?}");
+    assert!(error.is_retryable());
+    assert!(error.status().is_none());
+}
+
+#[tokio::test]
+async fn deserialization_error_reports_path_and_body() {
+    let mut quote = serde_json::to_value(quote_response()).unwrap();
+    quote["outAmount"] = "5 SOL".into();
+    let body = quote.to_string();
+    let server = MockServer::start(vec![response(200, &[], &body)]).await;
+
+    let error = server.client().quote(quote_request()).await.unwrap_err();
+
+    let ClientError::DeserializationError {
+        path,
+        body: error_body,
+        ..
+    } = &error
+    else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(path, "outAmount");
+    assert_eq!(*error_body, body);
+    assert!(error
+        .to_string()
+        .starts_with("Failed to deserialize response at outAmount: "));
+    assert!(!error.is_retryable());
+    assert!(error.status().is_none());
+    assert_eq!(server.request_count(), 1);
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use common::{instruction, swap_instructions};
+use jupiter_swap_api_client::{
+    assembler::compile_transaction,
+    estimate::{TransactionEstimate, MAX_TRANSACTION_ACCOUNTS},
+};
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{legacy, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+fn accounts(count: usize) -> Vec<Pubkey> {
+    (0..count).map(|_| Pubkey::new_unique()).collect()
+}
+
+fn lookup_table(addresses: &[Pubkey]) -> AddressLookupTableAccount {
+    AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: addresses.to_vec(),
+    }
+}
+
+#[test]
+fn serialized_size_is_the_bincode_length() {
+    let payer = Pubkey::new_unique();
+    let amm_accounts = accounts(20);
+    let swap_instructions = swap_instructions(&payer, &amm_accounts);
+    let instructions = swap_instructions
+        .instructions()
+        .map(|(_, instruction)| instruction.clone())
+        .collect::<Vec<_>>();
+
+    for lookup_tables in [vec![], vec![lookup_table(&amm_accounts[..12])]] {
+        let estimate = TransactionEstimate::new(&payer, &instructions, &lookup_tables).unwrap();
+        // The blockhash and signatures have a fixed size
+        let transaction =
+            compile_transaction(&payer, &instructions, &lookup_tables, Hash::new_unique()).unwrap();
+        assert_eq!(
+            estimate.serialized_size,
+            bincode::serialize(&transaction).unwrap().len()
+        );
+        assert_eq!(
+            estimate,
+            TransactionEstimate::from_transaction(&transaction)
+        );
+        assert_eq!(
+            swap_instructions
+                .estimate(&payer, &[], &lookup_tables)
+                .unwrap(),
+            estimate
+        );
+    }
+}
+
+#[test]
+fn accounts_are_counted_with_and_without_lookup_tables() {
+    let payer = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+    let amm_accounts = accounts(10);
+    let instructions = [instruction(program_id, 1, &amm_accounts)];
+
+    let estimate = TransactionEstimate::new(&payer, &instructions, &[]).unwrap();
+    assert_eq!(
+        estimate,
+        TransactionEstimate {
+            serialized_size: estimate.serialized_size,
+            num_signatures: 1,
+            static_accounts: 12,
+            lookup_table_accounts: 0,
+            lookup_tables: 0,
+        }
+    );
+
+    // Accounts of the two tables are loaded from them, the ones in neither stay static
+    let lookup_tables = [
+        lookup_table(&amm_accounts[..5]),
+        lookup_table(&amm_accounts[5..8]),
+    ];
+    let with_lookup_tables =
+        TransactionEstimate::new(&payer, &instructions, &lookup_tables).unwrap();
+    assert_eq!(with_lookup_tables.num_signatures, 1);
+    assert_eq!(with_lookup_tables.static_accounts, 4);
+    assert_eq!(with_lookup_tables.lookup_table_accounts, 8);
+    assert_eq!(with_lookup_tables.lookup_tables, 2);
+    assert_eq!(
+        with_lookup_tables.unique_accounts(),
+        estimate.unique_accounts()
+    );
+    assert!(with_lookup_tables.serialized_size < estimate.serialized_size);
+
+    // Signers are never loaded from lookup tables
+    let signer = amm_accounts[0];
+    let signed_instruction =
+        Instruction::new_with_bytes(program_id, &[2], vec![AccountMeta::new(signer, true)]);
+    let with_signer = TransactionEstimate::new(
+        &payer,
+        &[instructions[0].clone(), signed_instruction],
+        &lookup_tables,
+    )
+    .unwrap();
+    assert_eq!(with_signer.num_signatures, 2);
+    assert_eq!(with_signer.static_accounts, 5);
+    assert_eq!(with_signer.lookup_table_accounts, 7);
+}
+
+#[test]
+fn legacy_transactions_have_no_lookup_table_accounts() {
+    let payer = Pubkey::new_unique();
+    let message = legacy::Message::new(
+        &[instruction(Pubkey::new_unique(), 1, &accounts(3))],
+        Some(&payer),
+    );
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::Legacy(message),
+    };
+
+    let estimate = TransactionEstimate::from_transaction(&transaction);
+
+    assert_eq!(estimate.static_accounts, 5);
+    assert_eq!(estimate.lookup_table_accounts, 0);
+    assert_eq!(estimate.lookup_tables, 0);
+    assert_eq!(
+        estimate.serialized_size,
+        bincode::serialize(&transaction).unwrap().len()
+    );
+}
+
+#[test]
+fn account_limit_is_exceeded_past_max_accounts() {
+    let payer = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+    // The payer and the program are static, the other accounts are loaded from a table
+    let at_limit = accounts(MAX_TRANSACTION_ACCOUNTS - 2);
+    let lookup_tables = [lookup_table(&at_limit)];
+
+    let estimate = TransactionEstimate::new(
+        &payer,
+        &[instruction(program_id, 1, &at_limit)],
+        &lookup_tables,
+    )
+    .unwrap();
+    assert_eq!(estimate.unique_accounts(), MAX_TRANSACTION_ACCOUNTS);
+    assert!(!estimate.exceeds_account_limit());
+
+    let over_limit = [at_limit.as_slice(), &[Pubkey::new_unique()]].concat();
+    let estimate = TransactionEstimate::new(
+        &payer,
+        &[instruction(program_id, 1, &over_limit)],
+        &lookup_tables,
+    )
+    .unwrap();
+    assert_eq!(estimate.unique_accounts(), MAX_TRANSACTION_ACCOUNTS + 1);
+    assert!(estimate.exceeds_account_limit());
+}
+
+#[test]
+fn packet_limit_is_checked_on_the_serialized_size() {
+    let estimate = |serialized_size| TransactionEstimate {
+        serialized_size,
+        num_signatures: 1,
+        static_accounts: 2,
+        lookup_table_accounts: 0,
+        lookup_tables: 0,
+    };
+
+    assert!(estimate(PACKET_DATA_SIZE).fits_in_packet());
+    assert_eq!(estimate(PACKET_DATA_SIZE).bytes_over_limit(), 0);
+    assert!(!estimate(PACKET_DATA_SIZE + 1).fits_in_packet());
+    assert_eq!(estimate(PACKET_DATA_SIZE + 40).bytes_over_limit(), 40);
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use jupiter_swap_api_client::{
+    executor::{
+        ConfirmationLevel, ExecutionOutcome, ExecutorConfig, ExecutorError, ExecutorRpc,
+        SignatureStatus, TransactionExecutor, SLIPPAGE_TOLERANCE_EXCEEDED,
+    },
+    verify::JUPITER_PROGRAM_ID,
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    message::{Message, VersionedMessage},
+    signature::{Keypair, Signature, Signer},
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+/// Returns the scripted statuses in order, the last one repeating, and a block height
+/// increasing by one on each call
+struct MockRpc {
+    statuses: Mutex<VecDeque<Option<SignatureStatus>>>,
+    block_height: AtomicU64,
+    sends: AtomicUsize,
+}
+
+impl MockRpc {
+    fn new(block_height: u64, statuses: Vec<Option<SignatureStatus>>) -> Self {
+        Self {
+            statuses: Mutex::new(statuses.into()),
+            block_height: AtomicU64::new(block_height),
+            sends: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl ExecutorRpc for MockRpc {
+    type Error = Infallible;
+
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, Self::Error> {
+        self.sends.fetch_add(1, Ordering::SeqCst);
+        Ok(transaction.signatures[0])
+    }
+
+    async fn get_signature_status(
+        &self,
+        _signature: &Signature,
+    ) -> Result<Option<SignatureStatus>, Self::Error> {
+        let mut statuses = self.statuses.lock().unwrap();
+        Ok(if statuses.len() > 1 {
+            statuses.pop_front().flatten()
+        } else {
+            statuses.front().cloned().flatten()
+        })
+    }
+
+    async fn get_block_height(&self) -> Result<u64, Self::Error> {
+        Ok(self.block_height.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
+fn config() -> ExecutorConfig {
+    ExecutorConfig {
+        rebroadcast_interval: Duration::from_secs(1),
+        poll_interval: Duration::from_millis(500),
+        confirmation_level: ConfirmationLevel::Confirmed,
+    }
+}
+
+fn swap_transaction() -> VersionedTransaction {
+    let payer = Keypair::new();
+    let instruction = Instruction::new_with_bytes(JUPITER_PROGRAM_ID, &[], vec![]);
+    let message =
+        Message::new_with_blockhash(&[instruction], Some(&payer.pubkey()), &Hash::default());
+    VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&payer]).unwrap()
+}
+
+fn status(
+    confirmation_level: ConfirmationLevel,
+    error: Option<TransactionError>,
+) -> Option<SignatureStatus> {
+    Some(SignatureStatus {
+        slot: 42,
+        confirmation_level,
+        error,
+    })
+}
+
+#[tokio::test(start_paused = true)]
+async fn transaction_lands_after_rebroadcasts() {
+    let transaction = swap_transaction();
+    let rpc = MockRpc::new(
+        100,
+        vec![
+            None,
+            None,
+            None,
+            None,
+            status(ConfirmationLevel::Processed, None),
+            status(ConfirmationLevel::Confirmed, None),
+        ],
+    );
+    let executor = TransactionExecutor::with_config(rpc, config());
+
+    let outcome = executor.execute(&transaction, 200).await.unwrap();
+
+    assert_eq!(
+        outcome,
+        ExecutionOutcome::Landed {
+            signature: transaction.signatures[0],
+            slot: 42,
+            confirmation_level: ConfirmationLevel::Confirmed,
+        }
+    );
+    // Initial send and a rebroadcast every other poll while unprocessed
+    assert_eq!(executor.rpc().sends.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_transaction_reports_instruction_error() {
+    let transaction = swap_transaction();
+    let error = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(SLIPPAGE_TOLERANCE_EXCEEDED),
+    );
+    let rpc = MockRpc::new(
+        100,
+        vec![None, status(ConfirmationLevel::Processed, Some(error))],
+    );
+
+    let outcome = TransactionExecutor::with_config(rpc, config())
+        .execute(&transaction, 200)
+        .await
+        .unwrap();
+
+    let ExecutionOutcome::Failed {
+        failed_instruction: Some(failed_instruction),
+        ..
+    } = outcome
+    else {
+        panic!("expected a failed instruction, got {outcome:?}");
+    };
+    assert_eq!(failed_instruction.index, 0);
+    assert_eq!(failed_instruction.program_id, Some(JUPITER_PROGRAM_ID));
+    assert!(failed_instruction.is_slippage_exceeded());
+}
+
+#[tokio::test(start_paused = true)]
+async fn transaction_expires_past_last_valid_block_height() {
+    let transaction = swap_transaction();
+    let rpc = MockRpc::new(100, vec![None]);
+
+    let outcome = TransactionExecutor::with_config(rpc, config())
+        .execute(&transaction, 103)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome,
+        ExecutionOutcome::Expired {
+            signature: transaction.signatures[0],
+            last_valid_block_height: 103,
+        }
+    );
+}
+
+#[tokio::test]
+async fn unsigned_transaction_is_rejected() {
+    let mut transaction = swap_transaction();
+    transaction.signatures[0] = Signature::default();
+
+    let result = TransactionExecutor::new(MockRpc::new(100, vec![None]))
+        .execute(&transaction, 200)
+        .await;
+
+    assert!(matches!(result, Err(ExecutorError::MissingSignature)));
+}
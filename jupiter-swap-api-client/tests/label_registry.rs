@@ -0,0 +1,126 @@
+mod common;
+
+use std::{collections::HashMap, time::Duration};
+
+use common::{
+    mock_server::{response, MockServer},
+    swap_instructions, USER,
+};
+use jupiter_swap_api_client::{
+    dexes::{DexLabel, DexSet},
+    label_registry::{CachedLabelRegistry, LabelRegistry},
+    route_plan_with_metadata::{RoutePlanStep, SwapInfo},
+    swap::SwapInstructionKind,
+    verify::JUPITER_PROGRAM_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+fn labels_body(labels: &[(Pubkey, &str)]) -> String {
+    serde_json::to_string(
+        &labels
+            .iter()
+            .map(|(program_id, label)| (program_id.to_string(), *label))
+            .collect::<HashMap<_, _>>(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn registry_resolves_labels_and_program_ids() {
+    let mut whirlpool_program_ids = [Pubkey::new_unique(), Pubkey::new_unique()];
+    whirlpool_program_ids.sort();
+    let raydium = Pubkey::new_unique();
+    let new_dex = Pubkey::new_unique();
+    let registry = LabelRegistry::new(HashMap::from([
+        (whirlpool_program_ids[1], "Whirlpool".to_string()),
+        (whirlpool_program_ids[0], "Whirlpool".to_string()),
+        (raydium, "Raydium".to_string()),
+        (new_dex, "New Dex".to_string()),
+    ]));
+
+    assert_eq!(registry.label(&raydium), Some("Raydium"));
+    assert_eq!(registry.label(&Pubkey::new_unique()), None);
+    assert_eq!(registry.program_ids("Whirlpool"), whirlpool_program_ids);
+    assert_eq!(registry.program_ids("Raydium"), [raydium]);
+    assert!(registry.program_ids("Phoenix").is_empty());
+    assert_eq!(
+        registry.dexes(),
+        DexSet::from_iter([
+            DexLabel::Whirlpool,
+            DexLabel::Raydium,
+            DexLabel::Other("New Dex".to_string()),
+        ])
+    );
+
+    let step = RoutePlanStep {
+        swap_info: SwapInfo {
+            label: "Whirlpool".to_string(),
+            ..SwapInfo::default()
+        },
+        percent: 100,
+    };
+    assert_eq!(registry.step_program_ids(&step), whirlpool_program_ids);
+}
+
+#[test]
+fn instructions_are_labeled_by_program_id() {
+    let swap_instructions = swap_instructions(&USER, &[]);
+    let registry = LabelRegistry::new(HashMap::from([(JUPITER_PROGRAM_ID, "Jupiter".to_string())]));
+
+    let labeled = registry.label_instructions(&swap_instructions);
+
+    assert_eq!(labeled.len(), swap_instructions.instructions().count());
+    let labels = labeled
+        .iter()
+        .map(|labeled| (labeled.kind, labeled.label))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            (SwapInstructionKind::ComputeBudget, None),
+            (SwapInstructionKind::ComputeBudget, None),
+            (SwapInstructionKind::TokenLedger, Some("Jupiter")),
+            (SwapInstructionKind::Setup, None),
+            (SwapInstructionKind::Setup, None),
+            (SwapInstructionKind::Swap, Some("Jupiter")),
+            (SwapInstructionKind::Cleanup, None),
+            (SwapInstructionKind::Other, None),
+        ]
+    );
+    assert_eq!(*labeled[5].instruction, swap_instructions.swap_instruction);
+}
+
+#[tokio::test(start_paused = true)]
+async fn cached_registry_is_refreshed_after_ttl() {
+    let raydium = Pubkey::new_unique();
+    let whirlpool = Pubkey::new_unique();
+    let server = MockServer::start(vec![
+        response(200, &[], &labels_body(&[(raydium, "Raydium")])),
+        response(
+            200,
+            &[],
+            &labels_body(&[(raydium, "Raydium"), (whirlpool, "Whirlpool")]),
+        ),
+    ])
+    .await;
+    let cached_registry = CachedLabelRegistry::new(server.client(), Duration::from_secs(60));
+
+    let registry = cached_registry.get().await.unwrap();
+    assert_eq!(registry.label(&raydium), Some("Raydium"));
+    assert_eq!(registry.label(&whirlpool), None);
+
+    // Served from the cache until the ttl elapses, also by the clones
+    tokio::time::advance(Duration::from_secs(59)).await;
+    let cached = cached_registry.clone().get().await.unwrap();
+    assert_eq!(cached.label(&whirlpool), None);
+    assert_eq!(server.request_count(), 1);
+
+    tokio::time::advance(Duration::from_secs(1)).await;
+    let refreshed = cached_registry.get().await.unwrap();
+    assert_eq!(refreshed.label(&whirlpool), Some("Whirlpool"));
+    assert_eq!(refreshed.program_ids("Whirlpool"), [whirlpool]);
+    assert_eq!(server.request_count(), 2);
+
+    cached_registry.refresh().await.unwrap();
+    assert_eq!(server.request_count(), 3);
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{
+    mock_server::{response, MockServer},
+    quote_response, USDC_MINT,
+};
+use jupiter_swap_api_client::{
+    price::{PriceEntry, QuoteUsdValue, MAX_IDS_PER_REQUEST},
+    verify::NATIVE_MINT,
+};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+
+fn price_entry(mint: &Pubkey, price: &str) -> PriceEntry {
+    PriceEntry {
+        id: *mint,
+        price_type: "derivedPrice".to_string(),
+        price: price.parse().unwrap(),
+        extra_info: None,
+    }
+}
+
+/// Price response body, the mints without a price are null
+fn price_body(prices: &[(Pubkey, Option<&str>)]) -> String {
+    let data = prices
+        .iter()
+        .map(|(mint, price)| {
+            let entry = price.map(|price| price_entry(mint, price));
+            (mint.to_string(), serde_json::to_value(entry).unwrap())
+        })
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::json!({ "data": data, "timeTaken": 0.001 }).to_string()
+}
+
+#[tokio::test]
+async fn prices_are_batched_and_merged() {
+    let mut mints = (0..150).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    mints.sort();
+    let (first_chunk, second_chunk) = mints.split_at(MAX_IDS_PER_REQUEST);
+    let null_mint = first_chunk[10];
+    let first_body = price_body(
+        &first_chunk
+            .iter()
+            .map(|mint| (*mint, (*mint != null_mint).then_some("1.5")))
+            .collect::<Vec<_>>(),
+    );
+    let second_body = price_body(
+        &second_chunk
+            .iter()
+            .map(|mint| (*mint, Some("2")))
+            .collect::<Vec<_>>(),
+    );
+    let server = MockServer::start(vec![
+        response(200, &[], &first_body),
+        response(200, &[], &second_body),
+    ])
+    .await;
+
+    // Duplicates are requested once
+    let mut requested = mints.clone();
+    requested.reverse();
+    requested.push(mints[0]);
+    let prices = server.client().prices(&requested).await.unwrap();
+
+    let ids = |chunk: &[Pubkey]| {
+        chunk
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("%2C")
+    };
+    assert_eq!(
+        server.request_lines(),
+        vec![
+            format!(
+                "GET /price?ids={}&showExtraInfo=true HTTP/1.1",
+                ids(first_chunk)
+            ),
+            format!(
+                "GET /price?ids={}&showExtraInfo=true HTTP/1.1",
+                ids(second_chunk)
+            ),
+        ]
+    );
+    assert_eq!(prices.len(), 149);
+    assert!(!prices.contains_key(&null_mint));
+    assert_eq!(prices[&first_chunk[0]], price_entry(&first_chunk[0], "1.5"));
+    assert_eq!(
+        prices[&second_chunk[49]],
+        price_entry(&second_chunk[49], "2")
+    );
+}
+
+#[tokio::test]
+async fn no_request_is_sent_without_mints() {
+    let server = MockServer::start(vec![response(500, &[], "")]).await;
+
+    assert!(server.client().prices(&[]).await.unwrap().is_empty());
+    assert_eq!(server.request_count(), 0);
+}
+
+#[test]
+fn quote_usd_value_of_both_legs() {
+    let quote_response = quote_response();
+    let prices = HashMap::from([
+        (USDC_MINT, price_entry(&USDC_MINT, "1.0001")),
+        (NATIVE_MINT, price_entry(&NATIVE_MINT, "150")),
+    ]);
+    let decimals = HashMap::from([(USDC_MINT, 6), (NATIVE_MINT, 9)]);
+
+    // 1 USDC in for 0.005 SOL out
+    let usd_value = QuoteUsdValue::new(&quote_response, &prices, &decimals);
+    assert_eq!(usd_value.in_usd, Some(Decimal::new(10001, 4)));
+    assert_eq!(usd_value.out_usd, Some(Decimal::new(75, 2)));
+    assert_eq!(usd_value.usd_difference(), Some(Decimal::new(2501, 4)));
+
+    let without_price = HashMap::from([(USDC_MINT, price_entry(&USDC_MINT, "1.0001"))]);
+    let usd_value = QuoteUsdValue::new(&quote_response, &without_price, &decimals);
+    assert_eq!(usd_value.in_usd, Some(Decimal::new(10001, 4)));
+    assert_eq!(usd_value.out_usd, None);
+    assert_eq!(usd_value.usd_difference(), None);
+
+    let without_decimals = HashMap::from([(NATIVE_MINT, 9)]);
+    let usd_value = QuoteUsdValue::new(&quote_response, &prices, &without_decimals);
+    assert_eq!(usd_value.in_usd, None);
+    assert_eq!(usd_value.out_usd, Some(Decimal::new(75, 2)));
+}
+
+#[tokio::test]
+async fn quote_usd_value_resolves_prices_and_decimals() {
+    let mut mints = [USDC_MINT, NATIVE_MINT];
+    mints.sort();
+    let prices = price_body(&[(USDC_MINT, Some("1")), (NATIVE_MINT, Some("150"))]);
+    let tokens = serde_json::json!([
+        { "id": USDC_MINT.to_string(), "name": "USD Coin", "symbol": "USDC", "decimals": 6 },
+        { "id": NATIVE_MINT.to_string(), "name": "Wrapped SOL", "symbol": "SOL", "decimals": 9 },
+    ])
+    .to_string();
+    let server = MockServer::start(vec![
+        response(200, &[], &prices),
+        response(200, &[], &tokens),
+    ])
+    .await;
+
+    let usd_value = server
+        .client()
+        .quote_usd_value(&quote_response())
+        .await
+        .unwrap();
+
+    assert_eq!(usd_value.in_usd, Some(Decimal::ONE));
+    assert_eq!(usd_value.out_usd, Some(Decimal::new(75, 2)));
+    let request_lines = server.request_lines();
+    assert!(request_lines[0].starts_with("GET /price?ids="));
+    assert_eq!(
+        request_lines[1],
+        format!(
+            "GET /tokens/search?query={}%2C{} HTTP/1.1",
+            mints[0], mints[1]
+        )
+    );
+}
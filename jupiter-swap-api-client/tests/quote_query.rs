@@ -0,0 +1,296 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::USDC_MINT;
+use jupiter_swap_api_client::{
+    dexes::{DexLabel, DexSet},
+    quote::{ComputeUnitScore, QuoteRequest, QuoteRequestBuilder, QuoteRequestError, SwapMode},
+    quote_type::{QuoteArgs, QuoteType, RouteConstraints, RoutingConstraints},
+    verify::NATIVE_MINT,
+    ClientError,
+};
+use reqwest::Url;
+
+fn query_pairs(quote_request: &QuoteRequest) -> BTreeMap<String, String> {
+    let query = quote_request.to_query_string().unwrap();
+    let url = Url::parse(&format!("https://quote-api.jup.ag/v6/quote?{query}")).unwrap();
+    let pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+    let len = pairs.len();
+    let pairs = pairs.into_iter().collect::<BTreeMap<_, _>>();
+    assert_eq!(len, pairs.len(), "duplicated parameter in {query}");
+    pairs
+}
+
+fn expected(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn every_field_reaches_the_query() {
+    // No `..QuoteRequest::default()` so that adding a field fails to compile until it is covered here
+    let quote_request = QuoteRequest {
+        input_mint: USDC_MINT,
+        output_mint: NATIVE_MINT,
+        amount: 1_000_000,
+        swap_mode: Some(SwapMode::ExactOut),
+        slippage_bps: 50,
+        auto_slippage: Some(true),
+        max_auto_slippage_bps: Some(300),
+        compute_auto_slippage: true,
+        auto_slippage_collision_usd_value: Some(1_000),
+        minimize_slippage: Some(true),
+        platform_fee_bps: Some(20),
+        dexes: Some(DexSet::from_iter([
+            DexLabel::Whirlpool,
+            DexLabel::MeteoraDlmm,
+        ])),
+        excluded_dexes: Some(DexSet::from_iter([DexLabel::RaydiumClmm])),
+        only_direct_routes: Some(false),
+        as_legacy_transaction: Some(false),
+        restrict_intermediate_tokens: Some(true),
+        max_accounts: Some(40),
+        quote_type: Some(QuoteType::Metis),
+        quote_args: Some(
+            QuoteArgs::new()
+                .with("maxHops", 3)
+                .with("someArg", "someValue"),
+        ),
+        prefer_liquid_dexes: Some(true),
+        compute_unit_score: Some(ComputeUnitScore {
+            max_penalty_bps: Some(12.5),
+        }),
+        routing_constraints: Some(RoutingConstraints::Raw("constraints".into())),
+        token_category_based_intermediate_tokens: Some(true),
+    };
+
+    assert_eq!(
+        query_pairs(&quote_request),
+        expected(&[
+            ("inputMint", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+            ("outputMint", "So11111111111111111111111111111111111111112"),
+            ("amount", "1000000"),
+            ("swapMode", "ExactOut"),
+            ("slippageBps", "50"),
+            ("autoSlippage", "true"),
+            ("maxAutoSlippageBps", "300"),
+            ("computeAutoSlippage", "true"),
+            ("autoSlippageCollisionUsdValue", "1000"),
+            ("minimizeSlippage", "true"),
+            ("platformFeeBps", "20"),
+            ("dexes", "Meteora DLMM,Whirlpool"),
+            ("excludeDexes", "Raydium CLMM"),
+            ("onlyDirectRoutes", "false"),
+            ("asLegacyTransaction", "false"),
+            ("restrictIntermediateTokens", "true"),
+            ("maxAccounts", "40"),
+            ("quoteType", "metis"),
+            ("maxHops", "3"),
+            ("someArg", "someValue"),
+            ("preferLiquidDexes", "true"),
+            ("maxPenaltyBps", "12.5"),
+            ("routingConstraints", "constraints"),
+            ("tokenCategoryBasedIntermediateTokens", "true"),
+        ])
+    );
+}
+
+#[test]
+fn unset_options_are_omitted() {
+    let quote_request = QuoteRequest {
+        input_mint: USDC_MINT,
+        output_mint: NATIVE_MINT,
+        amount: 1_000_000,
+        slippage_bps: 50,
+        ..QuoteRequest::default()
+    };
+
+    assert_eq!(
+        query_pairs(&quote_request),
+        expected(&[
+            ("inputMint", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+            ("outputMint", "So11111111111111111111111111111111111111112"),
+            ("amount", "1000000"),
+            ("slippageBps", "50"),
+            ("computeAutoSlippage", "false"),
+        ])
+    );
+}
+
+#[test]
+fn empty_compute_unit_score_is_omitted() {
+    let quote_request = QuoteRequest {
+        compute_unit_score: Some(ComputeUnitScore::default()),
+        ..QuoteRequest::default()
+    };
+
+    let pairs = query_pairs(&quote_request);
+    assert!(!pairs.contains_key("maxPenaltyBps"));
+}
+
+#[test]
+fn typed_quote_options_are_serialized_as_strings() {
+    let quote_request = QuoteRequest::builder()
+        .input_mint(USDC_MINT)
+        .output_mint(NATIVE_MINT)
+        .amount(1_000_000)
+        .quote_type(QuoteType::Other("custom".into()))
+        .quote_arg("customArg", 7)
+        .routing_constraints(
+            RouteConstraints::new()
+                .with("customConstraint", true)
+                .with("limit", 2),
+        )
+        .build()
+        .unwrap();
+
+    let pairs = query_pairs(&quote_request);
+    assert_eq!(pairs["quoteType"], "custom");
+    assert_eq!(pairs["customArg"], "7");
+    assert_eq!(
+        pairs["routingConstraints"],
+        r#"{"customConstraint":true,"limit":2}"#
+    );
+
+    let constraints: RoutingConstraints =
+        serde_json::from_value(pairs["routingConstraints"].clone().into()).unwrap();
+    assert_eq!(
+        constraints,
+        RoutingConstraints::Constraints(
+            RouteConstraints::new()
+                .with("customConstraint", true)
+                .with("limit", 2)
+        )
+    );
+    let raw: RoutingConstraints = serde_json::from_value("constraints".into()).unwrap();
+    assert_eq!(raw, RoutingConstraints::Raw("constraints".into()));
+}
+
+#[test]
+fn quote_args_colliding_with_parameters_are_rejected() {
+    let builder = QuoteRequest::builder()
+        .input_mint(USDC_MINT)
+        .output_mint(NATIVE_MINT)
+        .amount(1_000_000);
+    for key in ["slippageBps", "maxPenaltyBps", "routingConstraints"] {
+        assert_eq!(
+            builder.clone().quote_arg(key, 1).build().unwrap_err(),
+            QuoteRequestError::QuoteArgCollision(key.to_string())
+        );
+    }
+
+    // Requests built without the builder are checked when serialized
+    let quote_request = QuoteRequest {
+        quote_args: Some(QuoteArgs::new().with("amount", 1)),
+        ..QuoteRequest::default()
+    };
+    let error = quote_request.to_query_string().unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::InvalidQuoteRequest(QuoteRequestError::QuoteArgCollision(ref key)) if key == "amount"
+    ));
+    assert!(!error.is_retryable());
+}
+
+fn builder() -> QuoteRequestBuilder {
+    QuoteRequest::builder()
+        .input_mint(USDC_MINT)
+        .output_mint(NATIVE_MINT)
+        .amount(1_000_000)
+        .slippage_bps(50)
+}
+
+#[test]
+fn builder_requires_both_mints() {
+    assert_eq!(
+        QuoteRequest::builder()
+            .output_mint(NATIVE_MINT)
+            .amount(1)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::MissingInputMint
+    );
+    assert_eq!(
+        QuoteRequest::builder()
+            .input_mint(USDC_MINT)
+            .amount(1)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::MissingOutputMint
+    );
+}
+
+#[test]
+fn builder_rejects_same_mints() {
+    assert_eq!(
+        builder().output_mint(USDC_MINT).build().unwrap_err(),
+        QuoteRequestError::SameMints(USDC_MINT)
+    );
+}
+
+#[test]
+fn builder_rejects_zero_amount() {
+    assert_eq!(
+        builder().amount(0).build().unwrap_err(),
+        QuoteRequestError::ZeroAmount
+    );
+}
+
+#[test]
+fn builder_rejects_slippage_over_max() {
+    assert!(builder().slippage_bps(10_000).build().is_ok());
+    assert_eq!(
+        builder().slippage_bps(10_001).build().unwrap_err(),
+        QuoteRequestError::SlippageTooHigh(10_001)
+    );
+}
+
+#[test]
+fn builder_rejects_max_auto_slippage_without_auto_slippage() {
+    assert_eq!(
+        builder().max_auto_slippage_bps(300).build().unwrap_err(),
+        QuoteRequestError::MaxAutoSlippageWithoutAutoSlippage
+    );
+    assert_eq!(
+        builder()
+            .auto_slippage(false)
+            .max_auto_slippage_bps(300)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::MaxAutoSlippageWithoutAutoSlippage
+    );
+    assert_eq!(
+        builder()
+            .auto_slippage(true)
+            .max_auto_slippage_bps(10_001)
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::MaxAutoSlippageTooHigh(10_001)
+    );
+    assert!(builder()
+        .auto_slippage(true)
+        .max_auto_slippage_bps(300)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn builder_rejects_overlapping_dexes() {
+    assert_eq!(
+        builder()
+            .dexes(DexSet::from_iter([
+                DexLabel::Whirlpool,
+                DexLabel::MeteoraDlmm
+            ]))
+            .excluded_dexes(DexSet::from_iter([
+                DexLabel::Whirlpool,
+                DexLabel::RaydiumClmm
+            ]))
+            .build()
+            .unwrap_err(),
+        QuoteRequestError::OverlappingDexes(DexSet::from_iter([DexLabel::Whirlpool]))
+    );
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use jupiter_swap_api_client::{
+    rate_limit::{RateLimit, RateLimitError, RateLimiter},
+    Endpoint,
+};
+use tokio::time::Instant;
+
+/// Time waited by `acquire`, the clock only advances while sleeping as it is paused
+async fn acquire(rate_limiter: &RateLimiter, endpoint: Endpoint) -> Duration {
+    let start = Instant::now();
+    rate_limiter.acquire(endpoint).await;
+    start.elapsed()
+}
+
+#[test]
+fn invalid_rates_are_rejected() {
+    for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            RateLimit::new(rate, 1),
+            Err(RateLimitError::InvalidRate(_))
+        ));
+    }
+    assert!(RateLimit::per_minute(0).is_err());
+    assert_eq!(RateLimit::new(2.0, 0).unwrap().burst(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn burst_is_sent_at_once_then_rate_is_sustained() {
+    let rate_limiter = RateLimiter::new(RateLimit::new(2.0, 3).unwrap());
+
+    for _ in 0..3 {
+        assert_eq!(
+            acquire(&rate_limiter, Endpoint::Quote).await,
+            Duration::ZERO
+        );
+    }
+    assert_eq!(
+        acquire(&rate_limiter, Endpoint::Quote).await,
+        Duration::from_millis(500)
+    );
+    assert_eq!(
+        acquire(&rate_limiter, Endpoint::Quote).await,
+        Duration::from_millis(500)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn permits_are_refilled_up_to_the_burst() {
+    let rate_limiter = RateLimiter::new(RateLimit::new(1.0, 2).unwrap());
+    for _ in 0..2 {
+        acquire(&rate_limiter, Endpoint::Quote).await;
+    }
+
+    // Idle long enough to refill more than the burst
+    tokio::time::advance(Duration::from_secs(10)).await;
+
+    for _ in 0..2 {
+        assert_eq!(
+            acquire(&rate_limiter, Endpoint::Quote).await,
+            Duration::ZERO
+        );
+    }
+    assert_eq!(
+        acquire(&rate_limiter, Endpoint::Quote).await,
+        Duration::from_secs(1)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn clones_share_the_buckets() {
+    let rate_limiter = RateLimiter::new(RateLimit::new(1.0, 2).unwrap())
+        .with_endpoint_limit(Endpoint::Swap, RateLimit::new(1.0, 1).unwrap());
+    let clone = rate_limiter.clone();
+
+    assert_eq!(
+        acquire(&rate_limiter, Endpoint::Quote).await,
+        Duration::ZERO
+    );
+    assert_eq!(acquire(&clone, Endpoint::Tokens).await, Duration::ZERO);
+    assert_eq!(
+        acquire(&rate_limiter, Endpoint::Quote).await,
+        Duration::from_secs(1)
+    );
+
+    // The endpoint bucket is shared by the clones too, but not with the other endpoints
+    assert_eq!(acquire(&clone, Endpoint::Swap).await, Duration::ZERO);
+    assert_eq!(
+        acquire(&rate_limiter, Endpoint::Swap).await,
+        Duration::from_secs(1)
+    );
+}
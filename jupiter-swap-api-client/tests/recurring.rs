@@ -0,0 +1,207 @@
+mod common;
+
+use common::{
+    mock_server::{response, MockServer},
+    USDC_MINT, USER,
+};
+use jupiter_swap_api_client::{
+    recurring::{
+        CancelRecurringOrderRequest, CreateRecurringOrderRequest, PriceBasedParams,
+        RecurringDepositRequest, RecurringOrderParams, RecurringOrdersRequest, RecurringType,
+        RecurringTypeFilter, RecurringWithdrawRequest, TimeBasedParams, WithdrawMint,
+    },
+    trigger::OrderStatusFilter,
+    verify::NATIVE_MINT,
+};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+const TRANSACTION_RESPONSE: &str = r#"{"requestId":"c1d2e3","transaction":"AQID"}"#;
+
+#[test]
+fn time_based_params_are_externally_tagged() {
+    let create_request = CreateRecurringOrderRequest {
+        user: USER,
+        input_mint: USDC_MINT,
+        output_mint: NATIVE_MINT,
+        params: RecurringOrderParams::Time(TimeBasedParams {
+            in_amount: 100_000_000,
+            number_of_orders: 4,
+            interval: 86_400,
+            min_price: None,
+            max_price: Some(250.5),
+            start_at: None,
+        }),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&create_request).unwrap(),
+        json!({
+            "user": USER.to_string(),
+            "inputMint": USDC_MINT.to_string(),
+            "outputMint": NATIVE_MINT.to_string(),
+            "params": {
+                "time": {
+                    "inAmount": 100_000_000,
+                    "numberOfOrders": 4,
+                    "interval": 86_400,
+                    "minPrice": null,
+                    "maxPrice": 250.5,
+                    "startAt": null,
+                }
+            },
+        })
+    );
+}
+
+#[test]
+fn price_based_params_are_externally_tagged() {
+    let params = RecurringOrderParams::Price(PriceBasedParams {
+        deposit_amount: 1_000_000_000,
+        increment_usdc_value: 10_000_000,
+        interval: 3_600,
+        start_at: Some(1_760_000_000),
+    });
+
+    assert_eq!(
+        serde_json::to_value(&params).unwrap(),
+        json!({
+            "price": {
+                "depositAmount": 1_000_000_000,
+                "incrementUsdcValue": 10_000_000,
+                "interval": 3_600,
+                "startAt": 1_760_000_000,
+            }
+        })
+    );
+}
+
+#[test]
+fn cancel_body() {
+    let order = Pubkey::new_unique();
+    let cancel_request = CancelRecurringOrderRequest {
+        order,
+        user: USER,
+        recurring_type: RecurringType::Time,
+    };
+
+    assert_eq!(
+        serde_json::to_value(&cancel_request).unwrap(),
+        json!({
+            "order": order.to_string(),
+            "user": USER.to_string(),
+            "recurringType": "time",
+        })
+    );
+}
+
+#[tokio::test]
+async fn price_deposit_and_withdraw_bodies() {
+    let order = Pubkey::new_unique();
+    let deposit_request = RecurringDepositRequest {
+        order,
+        user: USER,
+        amount: 5_000_000,
+    };
+    assert_eq!(
+        serde_json::to_value(&deposit_request).unwrap(),
+        json!({
+            "order": order.to_string(),
+            "user": USER.to_string(),
+            "amount": 5_000_000,
+        })
+    );
+    let withdraw_all = RecurringWithdrawRequest {
+        order,
+        user: USER,
+        input_or_output: WithdrawMint::Out,
+        amount: None,
+    };
+    assert_eq!(
+        serde_json::to_value(&withdraw_all).unwrap(),
+        json!({
+            "order": order.to_string(),
+            "user": USER.to_string(),
+            "inputOrOutput": "Out",
+        })
+    );
+    let withdraw_some = RecurringWithdrawRequest {
+        input_or_output: WithdrawMint::In,
+        amount: Some(1_000),
+        ..withdraw_all
+    };
+    assert_eq!(
+        serde_json::to_value(&withdraw_some).unwrap(),
+        json!({
+            "order": order.to_string(),
+            "user": USER.to_string(),
+            "inputOrOutput": "In",
+            "amount": 1_000,
+        })
+    );
+
+    let server = MockServer::start(vec![response(200, &[], TRANSACTION_RESPONSE)]).await;
+    let client = server.client();
+    let deposit = client.recurring_deposit(&deposit_request).await.unwrap();
+    let withdraw = client.recurring_withdraw(&withdraw_some).await.unwrap();
+    assert_eq!(deposit.request_id, "c1d2e3");
+    assert_eq!(withdraw.transaction, [1, 2, 3]);
+    assert_eq!(
+        server.request_lines(),
+        vec![
+            "POST /recurring/priceDeposit HTTP/1.1".to_string(),
+            "POST /recurring/priceWithdraw HTTP/1.1".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn recurring_orders_query_and_response() {
+    let order = Pubkey::new_unique();
+    let body = json!({
+        "user": USER.to_string(),
+        "orderStatus": "active",
+        "time": [
+            {
+                "userPubkey": USER.to_string(),
+                "orderKey": order.to_string(),
+                "inputMint": USDC_MINT.to_string(),
+                "outputMint": NATIVE_MINT.to_string(),
+                "createdAt": "2025-06-01T10:00:00Z",
+                "updatedAt": "2025-06-02T10:00:00Z",
+                "openTx": "",
+                "closeTx": null,
+                "cycleFrequency": "86400",
+                "inDeposited": "100000000"
+            }
+        ],
+        "totalPages": 1,
+        "page": 1
+    })
+    .to_string();
+    let server = MockServer::start(vec![response(200, &[], &body)]).await;
+
+    let orders = server
+        .client()
+        .recurring_orders(&RecurringOrdersRequest {
+            user: USER,
+            order_status: OrderStatusFilter::Active,
+            recurring_type: RecurringTypeFilter::Time,
+            include_failed_tx: false,
+            page: Some(1),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.request_lines(),
+        vec![format!(
+            "GET /recurring/getRecurringOrders?user={USER}&orderStatus=active&recurringType=time&includeFailedTx=false&page=1 HTTP/1.1"
+        )]
+    );
+    assert!(orders.price.is_empty());
+    let time_order = &orders.time[0];
+    assert_eq!(time_order.order_key, order);
+    assert_eq!(time_order.close_tx, None);
+    assert_eq!(time_order.extra["cycleFrequency"], "86400");
+}
@@ -0,0 +1,66 @@
+use jupiter_swap_api_client::{
+    render::{RouteDot, RouteTree},
+    route_plan_with_metadata::{RoutePlanStep, SwapInfo},
+};
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+const SOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+const USDC: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const USDT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+const JUP: Pubkey = pubkey!("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN");
+
+fn step(label: &str, input_mint: Pubkey, output_mint: Pubkey, percent: u8) -> RoutePlanStep {
+    RoutePlanStep {
+        swap_info: SwapInfo {
+            amm_key: Pubkey::new_unique(),
+            label: label.to_string(),
+            input_mint,
+            output_mint,
+            in_amount: 1_000,
+            out_amount: 990,
+            fee_amount: 3,
+            fee_mint: input_mint,
+        },
+        percent,
+    }
+}
+
+#[test]
+fn route_tree_groups_steps_by_input_mint() {
+    let route_plan = vec![
+        step("Whirlpool", SOL, USDC, 60),
+        step("Meteora DLMM", SOL, USDT, 40),
+        step("Raydium CLMM", USDC, JUP, 100),
+        step("Whirlpool", USDT, JUP, 100),
+    ];
+
+    assert_eq!(
+        RouteTree::new(&route_plan).to_string(),
+        "\
+├─ So11…1112
+│  ├─ 60% Whirlpool -> EPjF…Dt1v: in 1000, out 990, fee 3 So11…1112
+│  └─ 40% Meteora DLMM -> Es9v…wNYB: in 1000, out 990, fee 3 So11…1112
+├─ EPjF…Dt1v
+│  └─ 100% Raydium CLMM -> JUPy…DvCN: in 1000, out 990, fee 3 EPjF…Dt1v
+└─ Es9v…wNYB
+   └─ 100% Whirlpool -> JUPy…DvCN: in 1000, out 990, fee 3 Es9v…wNYB
+"
+    );
+}
+
+#[test]
+fn route_dot_escapes_labels() {
+    let route_plan = vec![step(r#"Orca "V2" \ Legacy"#, SOL, USDC, 100)];
+
+    assert_eq!(
+        RouteDot::new(&route_plan).to_string(),
+        r#"digraph route {
+  rankdir=LR;
+  node [shape=ellipse];
+  "So11111111111111111111111111111111111111112" [label="So11…1112"];
+  "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" [label="EPjF…Dt1v"];
+  "So11111111111111111111111111111111111111112" -> "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" [label="Orca \"V2\" \\ Legacy 100%\nin 1000 out 990\nfee 3 So11…1112"];
+}
+"#
+    );
+}
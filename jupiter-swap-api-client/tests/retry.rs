@@ -0,0 +1,141 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{
+    mock_server::{fast_policy, response, MockServer},
+    swap_request,
+};
+use jupiter_swap_api_client::{retry::RetryPolicy, ClientError};
+use reqwest::StatusCode;
+use solana_sdk::pubkey::Pubkey;
+
+const LABELS: &str = r#"{"JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4":"Jupiter"}"#;
+
+#[tokio::test]
+async fn rate_limited_request_is_retried_after_retry_after() {
+    let server = MockServer::start(vec![
+        response(
+            429,
+            &["Retry-After: 1"],
+            r#"{"error":"Rate limit exceeded"}"#,
+        ),
+        response(200, &[], LABELS),
+    ])
+    .await;
+
+    let start = Instant::now();
+    let labels = server.client().program_id_to_label().await.unwrap();
+
+    assert_eq!(labels.len(), 1);
+    assert_eq!(server.request_count(), 2);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn server_errors_exhaust_max_attempts() {
+    let server = MockServer::start(vec![response(503, &[], "")]).await;
+
+    let error = server.client().program_id_to_label().await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        ClientError::RequestFailed {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            ..
+        }
+    ));
+    assert_eq!(server.request_count(), 3);
+}
+
+#[tokio::test]
+async fn swap_is_not_retried_by_default() {
+    let server = MockServer::start(vec![response(503, &[], ""), response(200, &[], "{}")]).await;
+
+    let error = server
+        .client()
+        .swap(&swap_request(Pubkey::new_unique()), None)
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+    assert_eq!(
+        server.request_lines(),
+        vec!["POST /swap HTTP/1.1".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn retry_policy_is_overridden_per_call() {
+    let server = MockServer::start(vec![response(503, &[], "")]).await;
+    let client = server.client();
+
+    client
+        .with_retry_policy(RetryPolicy::none())
+        .program_id_to_label()
+        .await
+        .unwrap_err();
+    assert_eq!(server.request_count(), 1);
+
+    // The original client keeps its policy
+    client.program_id_to_label().await.unwrap_err();
+    assert_eq!(server.request_count(), 4);
+
+    let retry_swap = RetryPolicy {
+        retry_non_idempotent: true,
+        max_attempts: 2,
+        ..fast_policy()
+    };
+    client
+        .with_retry_policy(retry_swap)
+        .swap(&swap_request(Pubkey::new_unique()), None)
+        .await
+        .unwrap_err();
+    assert_eq!(server.request_count(), 6);
+}
+
+#[test]
+fn backoff_doubles_up_to_max_backoff() {
+    let policy = RetryPolicy {
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.backoff(1, None), Duration::from_millis(200));
+    assert_eq!(policy.backoff(2, None), Duration::from_millis(400));
+    assert_eq!(policy.backoff(3, None), Duration::from_millis(800));
+    assert_eq!(policy.backoff(10, None), Duration::from_secs(5));
+    assert_eq!(policy.backoff(u32::MAX, None), Duration::from_secs(5));
+
+    assert_eq!(
+        policy.backoff(1, Some(Duration::from_secs(2))),
+        Duration::from_secs(2)
+    );
+    let ignore_retry_after = RetryPolicy {
+        respect_retry_after: false,
+        ..policy
+    };
+    assert_eq!(
+        ignore_retry_after.backoff(1, Some(Duration::from_secs(2))),
+        Duration::from_millis(200)
+    );
+}
+
+#[test]
+fn jittered_backoff_is_between_half_and_full_backoff() {
+    let policy = RetryPolicy::default();
+    for attempt in 1..=10 {
+        let full = policy
+            .initial_backoff
+            .saturating_mul(1 << (attempt - 1))
+            .min(policy.max_backoff);
+        for _ in 0..50 {
+            let backoff = policy.backoff(attempt, None);
+            assert!(
+                full / 2 <= backoff && backoff <= full,
+                "{backoff:?} for {full:?}"
+            );
+        }
+    }
+    // Retry-After is a lower bound of the jittered backoff
+    assert!(policy.backoff(1, Some(Duration::from_secs(1))) >= Duration::from_secs(1));
+}
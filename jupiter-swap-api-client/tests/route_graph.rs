@@ -0,0 +1,153 @@
+use std::collections::BTreeSet;
+
+use jupiter_swap_api_client::{
+    dexes::{DexLabel, DexSet},
+    route_graph::{RouteGraph, RouteGraphError},
+    route_plan_with_metadata::{RoutePlanStep, SwapInfo},
+};
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+const SOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+const USDC: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const USDT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+const JUP: Pubkey = pubkey!("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN");
+
+fn step(label: &str, input_mint: Pubkey, output_mint: Pubkey, percent: u8) -> RoutePlanStep {
+    RoutePlanStep {
+        swap_info: SwapInfo {
+            amm_key: Pubkey::new_unique(),
+            label: label.to_string(),
+            input_mint,
+            output_mint,
+            in_amount: 1_000,
+            out_amount: 1_000,
+            fee_amount: 1,
+            fee_mint: input_mint,
+        },
+        percent,
+    }
+}
+
+#[test]
+fn split_and_merge_route_is_valid() {
+    // SOL is split between USDC and USDT which are both swapped into JUP
+    let route_plan = vec![
+        step("Whirlpool", SOL, USDC, 60),
+        step("Meteora DLMM", SOL, USDT, 40),
+        step("Raydium CLMM", USDC, JUP, 100),
+        step("Whirlpool", USDT, JUP, 100),
+    ];
+
+    let graph = RouteGraph::new(&route_plan, SOL, JUP).unwrap();
+
+    assert_eq!(graph.stages.len(), 3);
+    assert_eq!(graph.stages[0].input_mint, SOL);
+    assert_eq!(graph.stages[0].splits.len(), 2);
+    assert_eq!(graph.stages[0].output_mints(), BTreeSet::from([USDC, USDT]));
+    assert_eq!(graph.hop_count(), 3);
+    assert!(!graph.is_direct());
+    assert_eq!(
+        graph.hops(),
+        // Output mints of a stage are sorted
+        vec![(SOL, USDC), (SOL, USDT), (USDC, JUP), (USDT, JUP)]
+    );
+    assert_eq!(graph.intermediate_mints(), BTreeSet::from([USDC, USDT]));
+    assert_eq!(graph.amm_keys().len(), 4);
+    assert_eq!(
+        graph.dexes(),
+        DexSet::from_iter([
+            DexLabel::Whirlpool,
+            DexLabel::MeteoraDlmm,
+            DexLabel::RaydiumClmm
+        ])
+    );
+}
+
+#[test]
+fn direct_route_is_valid() {
+    let graph = RouteGraph::new(&vec![step("Whirlpool", SOL, USDC, 100)], SOL, USDC).unwrap();
+
+    assert!(graph.is_direct());
+    assert!(graph.intermediate_mints().is_empty());
+}
+
+#[test]
+fn empty_route_plan_is_rejected() {
+    assert_eq!(
+        RouteGraph::new(&Vec::new(), SOL, USDC),
+        Err(RouteGraphError::EmptyRoutePlan)
+    );
+}
+
+#[test]
+fn splits_must_sum_to_100_percent() {
+    let route_plan = vec![
+        step("Whirlpool", SOL, USDC, 60),
+        step("Meteora DLMM", SOL, USDC, 30),
+    ];
+
+    assert_eq!(
+        RouteGraph::new(&route_plan, SOL, USDC),
+        Err(RouteGraphError::InvalidSplit {
+            stage: 0,
+            input_mint: SOL,
+            total: 90
+        })
+    );
+}
+
+#[test]
+fn stage_must_swap_a_produced_mint() {
+    let route_plan = vec![
+        step("Whirlpool", SOL, USDC, 100),
+        step("Whirlpool", USDT, JUP, 100),
+    ];
+
+    assert_eq!(
+        RouteGraph::new(&route_plan, SOL, JUP),
+        Err(RouteGraphError::UnexpectedInputMint {
+            stage: 1,
+            mint: USDT
+        })
+    );
+}
+
+#[test]
+fn route_must_not_produce_a_swapped_mint() {
+    let route_plan = vec![
+        step("Whirlpool", SOL, USDC, 100),
+        step("Whirlpool", USDC, SOL, 100),
+    ];
+
+    assert_eq!(
+        RouteGraph::new(&route_plan, SOL, SOL),
+        Err(RouteGraphError::Cycle {
+            stage: 1,
+            mint: SOL
+        })
+    );
+}
+
+#[test]
+fn every_intermediate_mint_must_be_swapped() {
+    let route_plan = vec![
+        step("Whirlpool", SOL, USDC, 60),
+        step("Meteora DLMM", SOL, USDT, 40),
+        step("Whirlpool", USDC, JUP, 100),
+    ];
+
+    assert_eq!(
+        RouteGraph::new(&route_plan, SOL, JUP),
+        Err(RouteGraphError::UnconsumedMint(USDT))
+    );
+}
+
+#[test]
+fn route_must_reach_the_output_mint() {
+    let route_plan = vec![step("Whirlpool", SOL, USDC, 100)];
+
+    assert_eq!(
+        RouteGraph::new(&route_plan, SOL, JUP),
+        Err(RouteGraphError::OutputMintNotReached(JUP))
+    );
+}
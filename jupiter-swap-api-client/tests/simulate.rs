@@ -0,0 +1,174 @@
+#![cfg(feature = "simulate")]
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::{quote_response, USDC_MINT, USER};
+use jupiter_swap_api_client::{
+    assembler::compile_transaction,
+    simulate::{AccountState, FakeSimulationBackend, SwapSimulator},
+    verify::{associated_token_address, JUPITER_PROGRAM_ID, NATIVE_MINT, TOKEN_PROGRAM_ID},
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+fn token_account(amount: u64) -> AccountState {
+    let mut data = vec![0; 165];
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    AccountState {
+        lamports: 2_039_280,
+        data,
+    }
+}
+
+fn wallet(lamports: u64) -> AccountState {
+    AccountState {
+        lamports,
+        data: Vec::new(),
+    }
+}
+
+fn usdc_account() -> Pubkey {
+    associated_token_address(&USER, &USDC_MINT, &TOKEN_PROGRAM_ID)
+}
+
+/// Swap paid by the user and loading its USDC account, not its wSOL account
+fn transaction() -> VersionedTransaction {
+    let swap_instruction = Instruction::new_with_bytes(
+        JUPITER_PROGRAM_ID,
+        &[],
+        vec![AccountMeta::new(usdc_account(), false)],
+    );
+    compile_transaction(&USER, &[swap_instruction], &[], Hash::default()).unwrap()
+}
+
+#[tokio::test]
+async fn balance_diffs_are_compared_with_the_quote() {
+    let usdc_account = usdc_account();
+    let backend = FakeSimulationBackend {
+        accounts_before: HashMap::from([
+            (usdc_account, token_account(3_000_000)),
+            (USER, wallet(10_000_000)),
+        ]),
+        accounts_after: HashMap::from([
+            (usdc_account, token_account(2_000_000)),
+            (USER, wallet(14_990_000)),
+        ]),
+        units_consumed: Some(120_000),
+        ..FakeSimulationBackend::default()
+    };
+
+    let report = SwapSimulator::new(backend)
+        .simulate(&transaction(), &USER, &quote_response())
+        .await
+        .unwrap();
+
+    assert!(report.is_success());
+    assert_eq!(report.units_consumed, Some(120_000));
+    assert_eq!(report.input_spent(), Some(1_000_000));
+    assert_eq!(report.output_received(), Some(4_990_000));
+    assert_eq!(report.out_amount_difference(), Some(-10_000));
+    assert!(report.within_threshold());
+}
+
+#[tokio::test]
+async fn output_below_threshold_is_reported() {
+    let backend = FakeSimulationBackend {
+        accounts_before: HashMap::from([(USER, wallet(10_000_000))]),
+        accounts_after: HashMap::from([(USER, wallet(14_000_000))]),
+        ..FakeSimulationBackend::default()
+    };
+
+    let report = SwapSimulator::new(backend)
+        .simulate(&transaction(), &USER, &quote_response())
+        .await
+        .unwrap();
+
+    assert_eq!(report.output_received(), Some(4_000_000));
+    assert!(!report.within_threshold());
+}
+
+#[tokio::test]
+async fn failed_simulation_has_no_balance_changes() {
+    let backend = FakeSimulationBackend {
+        accounts_before: HashMap::from([
+            (usdc_account(), token_account(3_000_000)),
+            (USER, wallet(10_000_000)),
+        ]),
+        accounts_after: HashMap::from([
+            (usdc_account(), token_account(2_000_000)),
+            (USER, wallet(14_990_000)),
+        ]),
+        error: Some(TransactionError::InsufficientFundsForFee),
+        ..FakeSimulationBackend::default()
+    };
+
+    let report = SwapSimulator::new(backend)
+        .simulate(&transaction(), &USER, &quote_response())
+        .await
+        .unwrap();
+
+    assert!(!report.is_success());
+    assert_eq!(report.input.pre, 3_000_000);
+    assert_eq!(report.input.post, None);
+    assert_eq!(report.output.post, None);
+    assert_eq!(report.input_spent(), None);
+    assert_eq!(report.output_received(), None);
+    assert_eq!(report.out_amount_difference(), None);
+    assert!(!report.within_threshold());
+}
+
+#[tokio::test]
+async fn accounts_not_loaded_by_the_transaction_keep_their_balance() {
+    // The wSOL account of the user exists but the swap pays out in lamports
+    let wsol_account = associated_token_address(&USER, &NATIVE_MINT, &TOKEN_PROGRAM_ID);
+    let backend = FakeSimulationBackend {
+        accounts_before: HashMap::from([
+            (usdc_account(), token_account(3_000_000)),
+            (wsol_account, token_account(2_000_000)),
+            (USER, wallet(10_000_000)),
+        ]),
+        accounts_after: HashMap::from([
+            (usdc_account(), token_account(2_000_000)),
+            (wsol_account, token_account(2_000_000)),
+            (USER, wallet(14_990_000)),
+        ]),
+        ..FakeSimulationBackend::default()
+    };
+
+    let report = SwapSimulator::new(backend)
+        .simulate(&transaction(), &USER, &quote_response())
+        .await
+        .unwrap();
+
+    assert_eq!(report.output.pre, 12_000_000);
+    assert_eq!(report.output.post, Some(16_990_000));
+    assert_eq!(report.output_received(), Some(4_990_000));
+    assert!(report.within_threshold());
+}
+
+#[tokio::test]
+async fn closed_account_has_a_zero_balance() {
+    // The whole USDC balance is swapped and the account closed
+    let backend = FakeSimulationBackend {
+        accounts_before: HashMap::from([
+            (usdc_account(), token_account(1_000_000)),
+            (USER, wallet(10_000_000)),
+        ]),
+        accounts_after: HashMap::from([(USER, wallet(17_029_280))]),
+        ..FakeSimulationBackend::default()
+    };
+
+    let report = SwapSimulator::new(backend)
+        .simulate(&transaction(), &USER, &quote_response())
+        .await
+        .unwrap();
+
+    assert_eq!(report.input.post, Some(0));
+    assert_eq!(report.input_spent(), Some(1_000_000));
+}
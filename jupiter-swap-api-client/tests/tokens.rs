@@ -0,0 +1,103 @@
+mod common;
+
+use common::mock_server::{response, MockServer};
+use jupiter_swap_api_client::tokens::{TokenInfo, TokenTag, MAX_MINTS_PER_REQUEST};
+use solana_sdk::pubkey::Pubkey;
+
+fn token_info(mint: &Pubkey) -> TokenInfo {
+    TokenInfo {
+        mint: *mint,
+        name: format!("Token {mint}"),
+        symbol: "TKN".to_string(),
+        decimals: 6,
+        icon: None,
+        token_program: None,
+        is_verified: None,
+        tags: vec![TokenTag::Verified],
+        organic_score: None,
+    }
+}
+
+fn search_body(mints: &[Pubkey]) -> String {
+    serde_json::to_string(&mints.iter().map(token_info).collect::<Vec<_>>()).unwrap()
+}
+
+fn search_request_line(mints: &[Pubkey]) -> String {
+    let query = mints
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("%2C");
+    format!("GET /tokens/search?query={query} HTTP/1.1")
+}
+
+#[tokio::test]
+async fn uncached_mints_are_fetched_in_chunks_then_cached() {
+    let mut mints = (0..150).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    mints.sort();
+    let (first_chunk, second_chunk) = mints.split_at(MAX_MINTS_PER_REQUEST);
+    let server = MockServer::start(vec![
+        response(200, &[], &search_body(first_chunk)),
+        response(200, &[], &search_body(second_chunk)),
+    ])
+    .await;
+    let client = server.client();
+
+    let token_infos = client.token_infos(&mints).await.unwrap();
+
+    assert_eq!(token_infos.len(), 150);
+    assert_eq!(token_infos[&mints[120]], token_info(&mints[120]));
+    assert_eq!(
+        server.request_lines(),
+        vec![
+            search_request_line(first_chunk),
+            search_request_line(second_chunk)
+        ]
+    );
+
+    // Served from the cache, also by the clones of the client
+    let cached = client.clone().token_infos(&mints).await.unwrap();
+    assert_eq!(cached, token_infos);
+    assert_eq!(
+        client.token_info(&mints[0]).await.unwrap(),
+        Some(token_info(&mints[0]))
+    );
+    assert_eq!(server.request_count(), 2);
+}
+
+#[tokio::test]
+async fn only_uncached_mints_are_requested() {
+    let mut mints = (0..3).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    mints.sort();
+    let unknown_mint = Pubkey::new_unique();
+    // The search also matches tokens which were not asked for
+    let other_mint = Pubkey::new_unique();
+    let server = MockServer::start(vec![response(
+        200,
+        &[],
+        &search_body(&[mints[2], mints[0], other_mint]),
+    )])
+    .await;
+    let client = server.client();
+    client.token_cache.insert([token_info(&mints[1])]);
+
+    let token_infos = client
+        .token_infos(&[mints[0], mints[1], mints[2], mints[0]])
+        .await
+        .unwrap();
+
+    assert_eq!(token_infos.len(), 3);
+    assert!(!token_infos.contains_key(&other_mint));
+    assert_eq!(
+        server.request_lines(),
+        vec![search_request_line(&[mints[0], mints[2]])]
+    );
+
+    // Unknown mints are missing from the result and requested again
+    let token_infos = client.token_infos(&[mints[1], unknown_mint]).await.unwrap();
+    assert_eq!(token_infos.len(), 1);
+    assert_eq!(
+        server.request_lines()[1],
+        search_request_line(&[unknown_mint])
+    );
+}
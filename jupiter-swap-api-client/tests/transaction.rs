@@ -0,0 +1,180 @@
+mod common;
+
+use jupiter_swap_api_client::{
+    assembler::compile_transaction,
+    swap::{SwapRequest, SwapResponse},
+    transaction::{encode_transaction, partial_sign, TransactionError},
+    verify::JUPITER_PROGRAM_ID,
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+
+fn swap_instruction(signers: &[Pubkey]) -> Instruction {
+    Instruction::new_with_bytes(
+        JUPITER_PROGRAM_ID,
+        &[1, 2, 3],
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new(*signer, true))
+            .chain([AccountMeta::new(Pubkey::new_unique(), false)])
+            .collect(),
+    )
+}
+
+fn v0_transaction(payer: &Pubkey, signers: &[Pubkey]) -> VersionedTransaction {
+    compile_transaction(payer, &[swap_instruction(signers)], &[], Hash::new_unique()).unwrap()
+}
+
+fn legacy_transaction(payer: &Pubkey) -> VersionedTransaction {
+    let message =
+        Message::new_with_blockhash(&[swap_instruction(&[])], Some(payer), &Hash::new_unique());
+    VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::Legacy(message),
+    }
+}
+
+fn swap_response(transaction: &VersionedTransaction) -> SwapResponse {
+    SwapResponse {
+        swap_transaction: encode_transaction(transaction).unwrap(),
+        last_valid_block_height: 100,
+        prioritization_fee_lamports: 0,
+        compute_unit_limit: 200_000,
+        prioritization_type: None,
+        dynamic_slippage_report: None,
+        simulation_error: None,
+    }
+}
+
+fn swap_request(user: Pubkey, as_legacy_transaction: bool) -> SwapRequest {
+    let mut swap_request = common::swap_request(user);
+    swap_request.config.as_legacy_transaction = as_legacy_transaction;
+    swap_request
+}
+
+#[test]
+fn swap_transaction_is_signed_for_its_request() {
+    let user = Keypair::new();
+    let response = swap_response(&v0_transaction(&user.pubkey(), &[]));
+
+    let transaction = response
+        .sign_for_request(&swap_request(user.pubkey(), false), &[&user])
+        .unwrap();
+
+    assert_eq!(transaction.verify_with_results(), vec![true]);
+    assert_eq!(
+        response.versioned_transaction().unwrap().message,
+        transaction.message
+    );
+}
+
+#[test]
+fn legacy_transaction_is_signed_for_a_legacy_request() {
+    let user = Keypair::new();
+    let response = swap_response(&legacy_transaction(&user.pubkey()));
+
+    let transaction = response
+        .sign_for_request(&swap_request(user.pubkey(), true), &[&user])
+        .unwrap();
+
+    assert_eq!(transaction.verify_with_results(), vec![true]);
+}
+
+#[test]
+fn transaction_version_must_match_the_request() {
+    let user = Keypair::new();
+
+    let error = swap_response(&v0_transaction(&user.pubkey(), &[]))
+        .sign_for_request(&swap_request(user.pubkey(), true), &[&user])
+        .unwrap_err();
+    assert!(matches!(error, TransactionError::ExpectedLegacy));
+
+    let error = swap_response(&legacy_transaction(&user.pubkey()))
+        .sign_for_request(&swap_request(user.pubkey(), false), &[&user])
+        .unwrap_err();
+    assert!(matches!(error, TransactionError::ExpectedVersioned));
+}
+
+#[test]
+fn fee_payer_must_be_the_user() {
+    let user = Keypair::new();
+    let fee_payer = Keypair::new();
+    let response = swap_response(&v0_transaction(&fee_payer.pubkey(), &[user.pubkey()]));
+
+    let error = response
+        .sign_for_request(&swap_request(user.pubkey(), false), &[&user, &fee_payer])
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransactionError::FeePayerMismatch { fee_payer: payer, user: expected_user }
+            if payer == fee_payer.pubkey() && expected_user == user.pubkey()
+    ));
+}
+
+#[test]
+fn user_must_be_one_of_the_signers() {
+    let user = Keypair::new();
+    let other = Keypair::new();
+    let response = swap_response(&v0_transaction(&user.pubkey(), &[]));
+
+    let error = response
+        .sign_for_request(&swap_request(user.pubkey(), false), &[&other])
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransactionError::SignerMismatch { user: expected_user, signers }
+            if expected_user == user.pubkey() && signers == vec![other.pubkey()]
+    ));
+}
+
+#[test]
+fn signer_not_required_by_the_transaction_is_rejected() {
+    let user = Keypair::new();
+    let stranger = Keypair::new();
+    let response = swap_response(&v0_transaction(&user.pubkey(), &[]));
+
+    let error = response.sign(&[&user, &stranger]).unwrap_err();
+
+    assert!(matches!(
+        error,
+        TransactionError::UnexpectedSigner(signer) if signer == stranger.pubkey()
+    ));
+}
+
+#[test]
+fn every_required_signature_must_be_provided() {
+    let user = Keypair::new();
+    let co_signer = Keypair::new();
+    let response = swap_response(&v0_transaction(&user.pubkey(), &[co_signer.pubkey()]));
+
+    let error = response.sign(&[&user]).unwrap_err();
+    assert!(matches!(
+        error,
+        TransactionError::MissingSigners(missing) if missing == vec![co_signer.pubkey()]
+    ));
+
+    // A signature added beforehand by the other signer is kept
+    let mut transaction = response.versioned_transaction().unwrap();
+    partial_sign(&mut transaction, &[&co_signer]).unwrap();
+    let transaction = swap_response(&transaction).sign(&[&user]).unwrap();
+    assert_eq!(transaction.verify_with_results(), vec![true, true]);
+}
+
+#[test]
+fn undecodable_transaction_is_rejected() {
+    let mut response = swap_response(&v0_transaction(&Pubkey::new_unique(), &[]));
+    response.swap_transaction.truncate(10);
+
+    assert!(matches!(
+        response.versioned_transaction(),
+        Err(TransactionError::Bincode(_))
+    ));
+}
@@ -0,0 +1,219 @@
+mod common;
+
+use common::{
+    mock_server::{response, MockServer},
+    USDC_MINT, USER,
+};
+use jupiter_swap_api_client::{
+    trigger::{
+        CancelTriggerOrderRequest, CancelTriggerOrderResponse, CancelTriggerOrdersRequest,
+        CancelTriggerOrdersResponse, CreateTriggerOrderRequest, CreateTriggerOrderResponse,
+        OrderStatusFilter, TriggerOrderParams, TriggerOrderStatus, TriggerOrdersRequest,
+    },
+    verify::NATIVE_MINT,
+};
+use serde_json::json;
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+const ORDER: Pubkey = pubkey!("EdNN8GZBqKKXD1hLwTHDPQNuNf2eJGUxXBhfwL7z9tFq");
+
+#[test]
+fn create_order_body() {
+    let fee_account = Pubkey::new_unique();
+    let create_request = CreateTriggerOrderRequest {
+        input_mint: NATIVE_MINT,
+        output_mint: USDC_MINT,
+        maker: USER,
+        payer: USER,
+        params: TriggerOrderParams {
+            making_amount: 1_000_000_000,
+            taking_amount: 200_000_000,
+            expired_at: Some(1_760_000_000),
+            slippage_bps: None,
+            fee_bps: Some(10),
+        },
+        compute_unit_price: None,
+        fee_account: Some(fee_account),
+        wrap_and_unwrap_sol: Some(true),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&create_request).unwrap(),
+        json!({
+            "inputMint": NATIVE_MINT.to_string(),
+            "outputMint": USDC_MINT.to_string(),
+            "maker": USER.to_string(),
+            "payer": USER.to_string(),
+            "params": {
+                "makingAmount": "1000000000",
+                "takingAmount": "200000000",
+                "expiredAt": "1760000000",
+                "feeBps": "10",
+            },
+            "feeAccount": fee_account.to_string(),
+            "wrapAndUnwrapSol": true,
+        })
+    );
+}
+
+#[test]
+fn create_order_response() {
+    let create_response: CreateTriggerOrderResponse = serde_json::from_value(json!({
+        "order": ORDER.to_string(),
+        "transaction": "AQID",
+        "requestId": "370100dd-1a85-421b-9278-27f0961ae5f4",
+    }))
+    .unwrap();
+
+    assert_eq!(create_response.order, ORDER);
+    assert_eq!(create_response.transaction, [1, 2, 3]);
+    assert_eq!(
+        create_response.request_id,
+        "370100dd-1a85-421b-9278-27f0961ae5f4"
+    );
+}
+
+#[test]
+fn cancel_order_body_and_response() {
+    let cancel_request = CancelTriggerOrderRequest {
+        maker: USER,
+        order: ORDER,
+        compute_unit_price: Some(1_000),
+    };
+    assert_eq!(
+        serde_json::to_value(&cancel_request).unwrap(),
+        json!({
+            "maker": USER.to_string(),
+            "order": ORDER.to_string(),
+            "computeUnitPrice": 1_000,
+        })
+    );
+
+    let cancel_response: CancelTriggerOrderResponse = serde_json::from_value(json!({
+        "transaction": "AQID",
+        "requestId": "a4b5c6",
+    }))
+    .unwrap();
+    assert_eq!(cancel_response.transaction, [1, 2, 3]);
+    assert_eq!(cancel_response.request_id, "a4b5c6");
+}
+
+#[test]
+fn cancel_orders_body_and_response() {
+    let cancel_all = CancelTriggerOrdersRequest {
+        maker: USER,
+        orders: Vec::new(),
+        compute_unit_price: None,
+    };
+    // Without orders all the open orders of the maker are cancelled
+    assert_eq!(
+        serde_json::to_value(&cancel_all).unwrap(),
+        json!({ "maker": USER.to_string() })
+    );
+    let other_order = Pubkey::new_unique();
+    let cancel_some = CancelTriggerOrdersRequest {
+        orders: vec![ORDER, other_order],
+        ..cancel_all
+    };
+    assert_eq!(
+        serde_json::to_value(&cancel_some).unwrap(),
+        json!({
+            "maker": USER.to_string(),
+            "orders": [ORDER.to_string(), other_order.to_string()],
+        })
+    );
+
+    let cancel_response: CancelTriggerOrdersResponse = serde_json::from_value(json!({
+        "transactions": ["AQID", "BAU="],
+        "requestId": "a4b5c6",
+    }))
+    .unwrap();
+    let transactions = cancel_response
+        .transactions
+        .into_iter()
+        .map(|transaction| transaction.0)
+        .collect::<Vec<_>>();
+    assert_eq!(transactions, [vec![1, 2, 3], vec![4, 5]]);
+}
+
+#[tokio::test]
+async fn trigger_orders_query_and_response() {
+    let body = json!({
+        "orders": [
+            {
+                "userPubkey": USER.to_string(),
+                "orderKey": ORDER.to_string(),
+                "inputMint": NATIVE_MINT.to_string(),
+                "outputMint": USDC_MINT.to_string(),
+                "makingAmount": "1",
+                "takingAmount": "200",
+                "remainingMakingAmount": "0.5",
+                "remainingTakingAmount": "100",
+                "rawMakingAmount": "1000000000",
+                "rawTakingAmount": "200000000",
+                "rawRemainingMakingAmount": "500000000",
+                "rawRemainingTakingAmount": "100000000",
+                "slippageBps": "0",
+                "expiredAt": null,
+                "createdAt": "2025-06-01T10:00:00Z",
+                "updatedAt": "2025-06-01T11:00:00Z",
+                "status": "Open",
+                "openTx": "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW",
+                "closeTx": "",
+                "trades": []
+            }
+        ],
+        "totalPages": 3,
+        "page": 2,
+        "user": USER.to_string(),
+        "orderStatus": "active"
+    })
+    .to_string();
+    let server = MockServer::start(vec![response(200, &[], &body)]).await;
+    let client = server.client();
+
+    let orders = client
+        .trigger_orders(&TriggerOrdersRequest {
+            user: USER,
+            order_status: OrderStatusFilter::Active,
+            page: Some(2),
+            input_mint: Some(NATIVE_MINT),
+            output_mint: None,
+        })
+        .await
+        .unwrap();
+    client
+        .trigger_orders(&TriggerOrdersRequest {
+            user: USER,
+            order_status: OrderStatusFilter::History,
+            page: None,
+            input_mint: None,
+            output_mint: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.request_lines(),
+        vec![
+            format!(
+                "GET /trigger/getTriggerOrders?user={USER}&orderStatus=active&page=2&inputMint={NATIVE_MINT} HTTP/1.1"
+            ),
+            format!("GET /trigger/getTriggerOrders?user={USER}&orderStatus=history HTTP/1.1"),
+        ]
+    );
+    assert_eq!(orders.total_pages, 3);
+    assert_eq!(orders.page, 2);
+    let order = &orders.orders[0];
+    assert_eq!(order.order_key, ORDER);
+    assert_eq!(order.raw_remaining_making_amount, 500_000_000);
+    assert_eq!(order.slippage_bps, Some(0));
+    assert_eq!(order.expired_at, None);
+    assert_eq!(order.status, TriggerOrderStatus::Open);
+}
+
+#[test]
+fn unknown_order_status_is_kept_as_unknown() {
+    let status: TriggerOrderStatus = serde_json::from_value(json!("Expired")).unwrap();
+    assert_eq!(status, TriggerOrderStatus::Unknown);
+}
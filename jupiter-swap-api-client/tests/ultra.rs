@@ -0,0 +1,216 @@
+mod common;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use common::{
+    mock_server::{response, MockServer},
+    USDC_MINT,
+};
+use jupiter_swap_api_client::{
+    quote::SwapMode,
+    transaction::TransactionError,
+    ultra::{
+        UltraError, UltraExecuteRequest, UltraExecuteResponse, UltraExecuteStatus,
+        UltraOrderRequest, UltraOrderResponse,
+    },
+    verify::NATIVE_MINT,
+};
+use serde_json::json;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+
+/// Order of 0.1 SOL for USDC without a taker, in the format of the documented example
+fn order_without_taker() -> serde_json::Value {
+    json!({
+        "mode": "ultra",
+        "inputMint": "So11111111111111111111111111111111111111112",
+        "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "inAmount": "100000000",
+        "outAmount": "16198753",
+        "otherAmountThreshold": "16117760",
+        "swapMode": "ExactIn",
+        "slippageBps": 50,
+        "priceImpactPct": "0",
+        "routePlan": [
+            {
+                "swapInfo": {
+                    "ammKey": "5BKxfWMbmYBAEWvyPZS9esPducUba9GqyMjtLCfbaqyF",
+                    "label": "Meteora DLMM",
+                    "inputMint": "So11111111111111111111111111111111111111112",
+                    "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                    "inAmount": "100000000",
+                    "outAmount": "16198753",
+                    "feeAmount": "24825",
+                    "feeMint": "So11111111111111111111111111111111111111112"
+                },
+                "percent": 100
+            }
+        ],
+        "feeMint": "So11111111111111111111111111111111111111112",
+        "feeBps": 1,
+        "taker": null,
+        "gasless": false,
+        "transaction": "",
+        "prioritizationType": "None",
+        "prioritizationFeeLamports": 0,
+        "requestId": "0197b4e3-5d2a-7c1f-8f2e-3c4b5a6d7e8f",
+        "swapType": "aggregator",
+        "router": "metis"
+    })
+}
+
+/// Unsigned transaction paid by `payer` and signed by `taker`, the payer signature already set
+fn gasless_transaction(payer: &Keypair, taker: &Pubkey) -> VersionedTransaction {
+    let instruction = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[1],
+        vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(*taker, true),
+        ],
+    );
+    let message = VersionedMessage::V0(
+        v0::Message::try_compile(&payer.pubkey(), &[instruction], &[], Hash::new_unique()).unwrap(),
+    );
+    let payer_signature = payer.sign_message(&message.serialize());
+    VersionedTransaction {
+        signatures: vec![payer_signature, Signature::default()],
+        message,
+    }
+}
+
+#[test]
+fn order_without_taker_has_no_transaction() {
+    let order: UltraOrderResponse = serde_json::from_value(order_without_taker()).unwrap();
+
+    assert_eq!(order.mode.as_deref(), Some("ultra"));
+    assert_eq!(order.input_mint, NATIVE_MINT);
+    assert_eq!(order.output_mint, USDC_MINT);
+    assert_eq!(order.in_amount, 100_000_000);
+    assert_eq!(order.out_amount, 16_198_753);
+    assert_eq!(order.other_amount_threshold, 16_117_760);
+    assert_eq!(order.swap_mode, SwapMode::ExactIn);
+    assert_eq!(order.route_plan[0].swap_info.label, "Meteora DLMM");
+    assert_eq!(order.fee_mint, Some(NATIVE_MINT));
+    assert_eq!(order.taker, None);
+    assert_eq!(order.transaction, None);
+    assert_eq!(order.router.as_deref(), Some("metis"));
+    assert!(matches!(
+        order.versioned_transaction(),
+        Err(UltraError::MissingTransaction)
+    ));
+    assert!(matches!(
+        order.sign(&Keypair::new()),
+        Err(UltraError::MissingTransaction)
+    ));
+}
+
+#[test]
+fn order_is_signed_in_the_taker_slot() {
+    let payer = Keypair::new();
+    let taker = Keypair::new();
+    let transaction = gasless_transaction(&payer, &taker.pubkey());
+    let mut order = order_without_taker();
+    order["taker"] = taker.pubkey().to_string().into();
+    order["gasless"] = true.into();
+    order["transaction"] = STANDARD
+        .encode(bincode::serialize(&transaction).unwrap())
+        .into();
+    let order: UltraOrderResponse = serde_json::from_value(order).unwrap();
+    assert_eq!(order.taker, Some(taker.pubkey()));
+    assert!(order.gasless);
+
+    let signed = order.sign(&taker).unwrap();
+
+    let message = signed.message.serialize();
+    assert_eq!(signed.message.static_account_keys()[1], taker.pubkey());
+    // The payer signature is kept and the taker one is added in its own slot
+    assert_eq!(signed.signatures[0], transaction.signatures[0]);
+    assert!(signed.signatures[1].verify(taker.pubkey().as_ref(), &message));
+    assert!(signed.signatures[0].verify(payer.pubkey().as_ref(), &message));
+
+    let stranger = Keypair::new();
+    assert!(matches!(
+        order.sign(&stranger),
+        Err(UltraError::Transaction(TransactionError::UnexpectedSigner(signer))) if signer == stranger.pubkey()
+    ));
+
+    let execute_request = UltraExecuteRequest::new(&signed, order.request_id.clone()).unwrap();
+    let body = serde_json::to_value(&execute_request).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "signedTransaction": STANDARD.encode(bincode::serialize(&signed).unwrap()),
+            "requestId": "0197b4e3-5d2a-7c1f-8f2e-3c4b5a6d7e8f",
+        })
+    );
+}
+
+#[test]
+fn execute_responses_are_deserialized() {
+    let success: UltraExecuteResponse = serde_json::from_value(json!({
+        "status": "Success",
+        "signature": "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW",
+        "slot": "323598314",
+        "code": 0,
+        "inputAmountResult": "100000000",
+        "outputAmountResult": "16198753",
+        "swapEvents": [
+            {
+                "inputMint": "So11111111111111111111111111111111111111112",
+                "inputAmount": "100000000",
+                "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "outputAmount": "16198753"
+            }
+        ]
+    }))
+    .unwrap();
+    assert_eq!(success.status, UltraExecuteStatus::Success);
+    assert!(success.signature.is_some());
+    assert_eq!(success.slot, Some(323_598_314));
+    assert_eq!(success.output_amount_result, Some(16_198_753));
+    assert_eq!(success.swap_events.len(), 1);
+    assert_eq!(success.swap_events[0].output_mint, USDC_MINT);
+    assert_eq!(success.swap_events[0].input_amount, 100_000_000);
+
+    let failed: UltraExecuteResponse = serde_json::from_value(json!({
+        "status": "Failed",
+        "signature": "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW",
+        "code": -1005,
+        "error": "Transaction expired"
+    }))
+    .unwrap();
+    assert_eq!(failed.status, UltraExecuteStatus::Failed);
+    assert_eq!(failed.code, -1005);
+    assert_eq!(failed.error.as_deref(), Some("Transaction expired"));
+    assert_eq!(failed.slot, None);
+    assert!(failed.swap_events.is_empty());
+}
+
+#[tokio::test]
+async fn order_request_omits_unset_parameters() {
+    let server =
+        MockServer::start(vec![response(200, &[], &order_without_taker().to_string())]).await;
+    let order_request = UltraOrderRequest {
+        input_mint: NATIVE_MINT,
+        output_mint: USDC_MINT,
+        amount: 100_000_000,
+        ..UltraOrderRequest::default()
+    };
+
+    let order = server.client().ultra_order(&order_request).await.unwrap();
+
+    assert_eq!(order.transaction, None);
+    assert_eq!(
+        server.request_lines(),
+        vec![format!(
+            "GET /ultra/order?inputMint={NATIVE_MINT}&outputMint={USDC_MINT}&amount=100000000 HTTP/1.1"
+        )]
+    );
+}
@@ -0,0 +1,612 @@
+mod common;
+
+use common::{quote_response, USDC_MINT, USER};
+use jupiter_swap_api_client::{
+    assembler::compile_transaction,
+    verify::{
+        associated_token_address, SwapVerifier, Violation, ASSOCIATED_TOKEN_PROGRAM_ID,
+        JUPITER_PROGRAM_ID, NATIVE_MINT, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+    },
+};
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Signature,
+    system_instruction, system_program,
+    transaction::VersionedTransaction,
+};
+
+const ATTACKER: Pubkey = pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const OTHER_PROGRAM: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+const LOOKUP_TABLE: Pubkey = pubkey!("D6XNrxMsDoABJVVY5YyHxJuAB6WGzYCXpZeKyNtqu2v4");
+const POOL: Pubkey = pubkey!("HcoJqG325TTifs6jyWvRJ9ET4pDu12Xrt2EQKZGFmuKX");
+const EVENT_AUTHORITY: Pubkey = pubkey!("D8cy77BBepLMngZx6ZukaTff5hCt1HrWyKk3Hnd9oitf");
+
+fn user_token_account(mint: &Pubkey) -> Pubkey {
+    associated_token_address(&USER, mint, &TOKEN_PROGRAM_ID)
+}
+
+fn token_instruction(data: Vec<u8>, accounts: &[Pubkey]) -> Instruction {
+    token_program_instruction(TOKEN_PROGRAM_ID, data, accounts)
+}
+
+fn token_program_instruction(
+    program_id: Pubkey,
+    data: Vec<u8>,
+    accounts: &[Pubkey],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id,
+        &data,
+        accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false))
+            .collect(),
+    )
+}
+
+/// Jupiter route instruction, sending the output to `destination_token_account` when set
+fn route_instruction(destination_token_account: Option<Pubkey>) -> Instruction {
+    Instruction::new_with_bytes(
+        JUPITER_PROGRAM_ID,
+        &[229, 23, 203, 151, 122, 227, 173, 42],
+        vec![
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(USER, true),
+            AccountMeta::new(user_token_account(&USDC_MINT), false),
+            AccountMeta::new(user_token_account(&NATIVE_MINT), false),
+            AccountMeta::new(
+                destination_token_account.unwrap_or(JUPITER_PROGRAM_ID),
+                false,
+            ),
+            AccountMeta::new_readonly(NATIVE_MINT, false),
+            AccountMeta::new_readonly(JUPITER_PROGRAM_ID, false),
+            AccountMeta::new_readonly(EVENT_AUTHORITY, false),
+            AccountMeta::new_readonly(JUPITER_PROGRAM_ID, false),
+            // Accounts of the route
+            AccountMeta::new(POOL, false),
+            AccountMeta::new_readonly(USDC_MINT, false),
+        ],
+    )
+}
+
+fn swap_instruction() -> Instruction {
+    route_instruction(None)
+}
+
+/// Instructions of a USDC to SOL swap unwrapping the SOL at the end
+fn jupiter_instructions() -> Vec<Instruction> {
+    let wsol_account = user_token_account(&NATIVE_MINT);
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+        ComputeBudgetInstruction::set_compute_unit_price(10_000),
+        Instruction::new_with_bytes(
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            &[1],
+            vec![
+                AccountMeta::new(USER, true),
+                AccountMeta::new(wsol_account, false),
+                AccountMeta::new_readonly(USER, false),
+                AccountMeta::new_readonly(NATIVE_MINT, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+        ),
+        swap_instruction(),
+        token_instruction(vec![9], &[wsol_account, USER, USER]),
+    ]
+}
+
+fn legacy_transaction(payer: &Pubkey, instructions: &[Instruction]) -> VersionedTransaction {
+    let message = Message::new(instructions, Some(payer));
+    VersionedTransaction {
+        signatures: vec![Signature::default(); usize::from(message.header.num_required_signatures)],
+        message: VersionedMessage::Legacy(message),
+    }
+}
+
+fn verify(instructions: &[Instruction]) -> Vec<Violation> {
+    SwapVerifier::new(USER)
+        .verify_transaction(
+            &legacy_transaction(&USER, instructions),
+            &quote_response(),
+            &[],
+        )
+        .violations
+}
+
+/// Jupiter swap followed by `instruction`
+fn verify_with(instruction: Instruction) -> Vec<Violation> {
+    let mut instructions = jupiter_instructions();
+    instructions.push(instruction);
+    verify(&instructions)
+}
+
+fn lookup_table() -> AddressLookupTableAccount {
+    AddressLookupTableAccount {
+        key: LOOKUP_TABLE,
+        addresses: vec![POOL, user_token_account(&USDC_MINT)],
+    }
+}
+
+#[test]
+fn jupiter_swap_has_no_violations() {
+    let report = SwapVerifier::new(USER).verify_transaction(
+        &legacy_transaction(&USER, &jupiter_instructions()),
+        &quote_response(),
+        &[],
+    );
+    assert!(report.is_ok(), "{:?}", report.violations);
+
+    let transaction = compile_transaction(
+        &USER,
+        &jupiter_instructions(),
+        &[lookup_table()],
+        Hash::default(),
+    )
+    .unwrap();
+    let report = SwapVerifier::new(USER).verify_transaction(
+        &transaction,
+        &quote_response(),
+        &[lookup_table()],
+    );
+    assert!(report.is_ok(), "{:?}", report.violations);
+}
+
+#[test]
+fn program_not_allowed() {
+    let instruction = Instruction::new_with_bytes(OTHER_PROGRAM, &[], vec![]);
+    assert_eq!(
+        verify_with(instruction.clone()),
+        vec![Violation::ProgramNotAllowed {
+            index: 5,
+            program_id: OTHER_PROGRAM
+        }]
+    );
+
+    let report = SwapVerifier::new(USER)
+        .allow_program(OTHER_PROGRAM)
+        .verify_transaction(
+            &legacy_transaction(&USER, &[swap_instruction(), instruction]),
+            &quote_response(),
+            &[],
+        );
+    assert!(report.is_ok());
+}
+
+#[test]
+fn fee_payer_mismatch() {
+    let report = SwapVerifier::new(USER).verify_transaction(
+        &legacy_transaction(&ATTACKER, &jupiter_instructions()),
+        &quote_response(),
+        &[],
+    );
+    assert_eq!(
+        report.violations,
+        vec![
+            Violation::FeePayerMismatch {
+                fee_payer: ATTACKER
+            },
+            Violation::UnexpectedSigner { signer: ATTACKER },
+        ]
+    );
+}
+
+#[test]
+fn unexpected_signer() {
+    let mut instruction = swap_instruction();
+    instruction.accounts.push(AccountMeta::new(ATTACKER, true));
+    assert_eq!(
+        verify_with(instruction),
+        vec![Violation::UnexpectedSigner { signer: ATTACKER }]
+    );
+}
+
+#[test]
+fn unexpected_sol_transfer() {
+    assert_eq!(
+        verify_with(system_instruction::transfer(&USER, &ATTACKER, 1_000)),
+        vec![Violation::UnexpectedSolTransfer {
+            index: 5,
+            destination: ATTACKER,
+            lamports: 1_000
+        }]
+    );
+
+    let report = SwapVerifier::new(USER)
+        .allow_destination(ATTACKER)
+        .verify_transaction(
+            &legacy_transaction(
+                &USER,
+                &[
+                    swap_instruction(),
+                    system_instruction::transfer(&USER, &ATTACKER, 1_000),
+                ],
+            ),
+            &quote_response(),
+            &[],
+        );
+    assert!(report.is_ok());
+}
+
+#[test]
+fn unexpected_token_transfer() {
+    let source = user_token_account(&USDC_MINT);
+    let mut amount = 1_000u64.to_le_bytes().to_vec();
+    amount.insert(0, 3);
+    assert_eq!(
+        verify_with(token_instruction(amount, &[source, ATTACKER, USER])),
+        vec![Violation::UnexpectedTokenTransfer {
+            index: 5,
+            destination: ATTACKER
+        }]
+    );
+
+    // Transfer checked with fee: extension, instruction, amount, decimals and fee
+    let mut data = vec![26, 1];
+    data.extend(1_000u64.to_le_bytes());
+    data.push(6);
+    data.extend(10u64.to_le_bytes());
+    assert_eq!(
+        verify_with(token_program_instruction(
+            TOKEN_2022_PROGRAM_ID,
+            data,
+            &[source, USDC_MINT, ATTACKER, USER]
+        )),
+        vec![Violation::UnexpectedTokenTransfer {
+            index: 5,
+            destination: ATTACKER
+        }]
+    );
+}
+
+#[test]
+fn unexpected_approval() {
+    let mut data = 1_000u64.to_le_bytes().to_vec();
+    data.insert(0, 4);
+    assert_eq!(
+        verify_with(token_instruction(
+            data,
+            &[user_token_account(&USDC_MINT), ATTACKER, USER]
+        )),
+        vec![Violation::UnexpectedApproval {
+            index: 5,
+            delegate: ATTACKER
+        }]
+    );
+}
+
+#[test]
+fn foreign_authority() {
+    let account = user_token_account(&USDC_MINT);
+    // Set authority: account owner, some new authority
+    let mut data = vec![6, 2, 1];
+    data.extend(ATTACKER.to_bytes());
+    assert_eq!(
+        verify_with(token_instruction(data, &[account, USER])),
+        vec![Violation::ForeignAuthority {
+            index: 5,
+            account,
+            new_authority: Some(ATTACKER)
+        }]
+    );
+
+    // Initialize account 3 with a foreign owner
+    let mut data = vec![18];
+    data.extend(ATTACKER.to_bytes());
+    assert_eq!(
+        verify_with(token_instruction(data, &[POOL, USDC_MINT])),
+        vec![Violation::ForeignAuthority {
+            index: 5,
+            account: POOL,
+            new_authority: Some(ATTACKER)
+        }]
+    );
+}
+
+#[test]
+fn foreign_close_destination() {
+    let account = user_token_account(&NATIVE_MINT);
+    assert_eq!(
+        verify_with(token_instruction(vec![9], &[account, ATTACKER, USER])),
+        vec![Violation::ForeignCloseDestination {
+            index: 5,
+            account,
+            destination: ATTACKER
+        }]
+    );
+}
+
+#[test]
+fn foreign_account_owner() {
+    assert_eq!(
+        verify_with(system_instruction::assign(&USER, &OTHER_PROGRAM)),
+        vec![Violation::ForeignAccountOwner {
+            index: 5,
+            account: USER,
+            owner: OTHER_PROGRAM
+        }]
+    );
+
+    let account = Pubkey::create_with_seed(&USER, "vault", &OTHER_PROGRAM).unwrap();
+    assert_eq!(
+        verify_with(system_instruction::create_account_with_seed(
+            &USER,
+            &account,
+            &USER,
+            "vault",
+            1_000_000,
+            0,
+            &OTHER_PROGRAM,
+        )),
+        vec![Violation::ForeignAccountOwner {
+            index: 5,
+            account,
+            owner: OTHER_PROGRAM
+        }]
+    );
+
+    // Temporary wrapped SOL account owned by the token program
+    let account = Pubkey::create_with_seed(&USER, "wsol", &TOKEN_PROGRAM_ID).unwrap();
+    assert!(verify_with(system_instruction::create_account_with_seed(
+        &USER,
+        &account,
+        &USER,
+        "wsol",
+        2_039_280,
+        165,
+        &TOKEN_PROGRAM_ID,
+    ))
+    .is_empty());
+}
+
+#[test]
+fn instruction_not_allowed() {
+    assert_eq!(
+        verify_with(system_instruction::withdraw_nonce_account(
+            &POOL, &USER, &ATTACKER, 1_000
+        )),
+        vec![Violation::InstructionNotAllowed {
+            index: 5,
+            program_id: system_program::id()
+        }]
+    );
+
+    // Burn
+    let mut data = 1_000u64.to_le_bytes().to_vec();
+    data.insert(0, 8);
+    assert_eq!(
+        verify_with(token_instruction(
+            data,
+            &[user_token_account(&USDC_MINT), USDC_MINT, USER]
+        )),
+        vec![Violation::InstructionNotAllowed {
+            index: 5,
+            program_id: TOKEN_PROGRAM_ID
+        }]
+    );
+}
+
+#[test]
+fn compute_unit_price_too_high() {
+    let report = SwapVerifier::new(USER)
+        .max_compute_unit_price(5_000)
+        .verify_transaction(
+            &legacy_transaction(&USER, &jupiter_instructions()),
+            &quote_response(),
+            &[],
+        );
+    assert_eq!(
+        report.violations,
+        vec![Violation::ComputeUnitPriceTooHigh {
+            index: 1,
+            price: 10_000,
+            max_price: 5_000
+        }]
+    );
+}
+
+#[test]
+fn unexpected_mint() {
+    let mint = OTHER_PROGRAM;
+    let instruction = Instruction::new_with_bytes(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        &[1],
+        vec![
+            AccountMeta::new(USER, true),
+            AccountMeta::new(user_token_account(&mint), false),
+            AccountMeta::new_readonly(USER, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    );
+    assert_eq!(
+        verify_with(instruction),
+        vec![Violation::UnexpectedMint { index: 5, mint }]
+    );
+}
+
+#[test]
+fn mint_not_referenced() {
+    let mut quote_response = quote_response();
+    quote_response.input_mint = OTHER_PROGRAM;
+    let report = SwapVerifier::new(USER).verify_transaction(
+        &legacy_transaction(&USER, &jupiter_instructions()),
+        &quote_response,
+        &[],
+    );
+    assert_eq!(
+        report.violations,
+        vec![Violation::MintNotReferenced(OTHER_PROGRAM)]
+    );
+}
+
+#[test]
+fn unresolved_lookup_table() {
+    let transaction = compile_transaction(
+        &USER,
+        &jupiter_instructions(),
+        &[lookup_table()],
+        Hash::default(),
+    )
+    .unwrap();
+
+    let report = SwapVerifier::new(USER).verify_transaction(&transaction, &quote_response(), &[]);
+    assert_eq!(
+        report.violations,
+        vec![Violation::UnresolvedLookupTable(LOOKUP_TABLE)]
+    );
+
+    // Stale table missing the second address
+    let stale_table = AddressLookupTableAccount {
+        key: LOOKUP_TABLE,
+        addresses: vec![POOL],
+    };
+    let report =
+        SwapVerifier::new(USER).verify_transaction(&transaction, &quote_response(), &[stale_table]);
+    assert_eq!(
+        report.violations,
+        vec![Violation::UnresolvedLookupTable(LOOKUP_TABLE)]
+    );
+}
+
+#[test]
+fn undecodable_instruction() {
+    let instruction = Instruction::new_with_bytes(system_program::id(), &[255], vec![]);
+    assert_eq!(
+        verify_with(instruction),
+        vec![Violation::UndecodableInstruction { index: 5 }]
+    );
+
+    // Out of range program index, it must not resolve to the system program
+    let mut transaction = legacy_transaction(&USER, &jupiter_instructions());
+    let VersionedMessage::Legacy(message) = &mut transaction.message else {
+        unreachable!()
+    };
+    message.instructions[3].program_id_index = 200;
+    let report = SwapVerifier::new(USER).verify_transaction(&transaction, &quote_response(), &[]);
+    assert_eq!(
+        report.violations,
+        vec![Violation::UndecodableInstruction { index: 3 }]
+    );
+}
+
+#[test]
+fn unexpected_swap_destination() {
+    let mut instructions = jupiter_instructions();
+    instructions[3] = route_instruction(Some(ATTACKER));
+    assert_eq!(
+        verify(&instructions),
+        vec![Violation::UnexpectedSwapDestination {
+            index: 3,
+            destination: ATTACKER
+        }]
+    );
+
+    // User destination account of another mint, without a destination token account
+    let mut route = swap_instruction();
+    route.accounts[3].pubkey = user_token_account(&USDC_MINT);
+    instructions[3] = route;
+    assert_eq!(
+        verify(&instructions),
+        vec![Violation::UnexpectedSwapDestination {
+            index: 3,
+            destination: user_token_account(&USDC_MINT)
+        }]
+    );
+
+    // Destination token account of the swap request
+    let destination_token_account = Pubkey::new_unique();
+    instructions[3] = route_instruction(Some(destination_token_account));
+    let report = SwapVerifier::new(USER)
+        .destination_token_account(destination_token_account)
+        .verify_transaction(
+            &legacy_transaction(&USER, &instructions),
+            &quote_response(),
+            &[],
+        );
+    assert!(report.is_ok(), "{:?}", report.violations);
+}
+
+#[test]
+fn shared_accounts_route_destination() {
+    let shared_accounts_route = |destination_token_account: Pubkey| {
+        Instruction::new_with_bytes(
+            JUPITER_PROGRAM_ID,
+            &[193, 32, 155, 51, 65, 214, 156, 129],
+            [
+                TOKEN_PROGRAM_ID,
+                Pubkey::new_unique(),
+                USER,
+                user_token_account(&USDC_MINT),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                destination_token_account,
+                USDC_MINT,
+                NATIVE_MINT,
+            ]
+            .into_iter()
+            .map(|account| AccountMeta::new(account, account == USER))
+            .collect(),
+        )
+    };
+    let mut instructions = jupiter_instructions();
+
+    instructions[3] = shared_accounts_route(user_token_account(&NATIVE_MINT));
+    assert!(verify(&instructions).is_empty());
+
+    instructions[3] = shared_accounts_route(ATTACKER);
+    assert_eq!(
+        verify(&instructions),
+        vec![Violation::UnexpectedSwapDestination {
+            index: 3,
+            destination: ATTACKER
+        }]
+    );
+}
+
+#[test]
+fn unknown_jupiter_instruction_is_not_allowed() {
+    let instruction = Instruction::new_with_bytes(JUPITER_PROGRAM_ID, &[1, 2, 3], vec![]);
+    assert_eq!(
+        verify_with(instruction),
+        vec![Violation::InstructionNotAllowed {
+            index: 5,
+            program_id: JUPITER_PROGRAM_ID
+        }]
+    );
+}
+
+#[test]
+fn recover_nested_is_not_allowed() {
+    let owner_token_account = user_token_account(&USDC_MINT);
+    let instruction = Instruction::new_with_bytes(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        &[2],
+        [
+            associated_token_address(&owner_token_account, &NATIVE_MINT, &TOKEN_PROGRAM_ID),
+            NATIVE_MINT,
+            ATTACKER,
+            owner_token_account,
+            USDC_MINT,
+            USER,
+            TOKEN_PROGRAM_ID,
+        ]
+        .into_iter()
+        .map(|account| AccountMeta::new(account, account == USER))
+        .collect(),
+    );
+    assert_eq!(
+        verify_with(instruction),
+        vec![Violation::InstructionNotAllowed {
+            index: 5,
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID
+        }]
+    );
+}